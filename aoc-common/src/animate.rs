@@ -0,0 +1,115 @@
+use crate::{Grid, Point, SparseGrid};
+
+/// The colours a frame can draw in, mapped onto the terminal's own palette.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Colour {
+    /// Whatever the terminal draws text in
+    #[default]
+    Plain,
+    Grey,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+/// One picture of a simulation: rows of coloured characters and a caption
+/// saying what is happening.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    rows: Vec<Vec<(char, Colour)>>,
+    caption: String,
+    focus: Option<Point<usize>>,
+}
+
+impl Frame {
+    pub fn new(caption: impl Into<String>) -> Self {
+        Self {
+            rows: Vec::new(),
+            caption: caption.into(),
+            focus: None,
+        }
+    }
+
+    /// Draws a dense grid, one cell per character.
+    pub fn from_grid<T, F>(grid: &Grid<T>, caption: impl Into<String>, mut cell: F) -> Self
+    where
+        F: FnMut(&T) -> (char, Colour),
+    {
+        let mut frame = Self::new(caption);
+        for row in grid.rows().take(grid.height()) {
+            frame.push_row(row.iter().map(&mut cell));
+        }
+        frame
+    }
+
+    /// Draws the part of a sparse grid within `bounds`, with `cell` given
+    /// None for positions that aren't set.
+    pub fn from_sparse<T, F>(
+        grid: &SparseGrid<T>,
+        (min, max): (Point<i64>, Point<i64>),
+        caption: impl Into<String>,
+        mut cell: F,
+    ) -> Self
+    where
+        F: FnMut(Option<&T>) -> (char, Colour),
+    {
+        let mut frame = Self::new(caption);
+        for y in min.y..=max.y {
+            frame.push_row((min.x..=max.x).map(|x| cell(grid.get(Point::new(x, y)))));
+        }
+        frame
+    }
+
+    pub fn push_row(&mut self, cells: impl IntoIterator<Item = (char, Colour)>) {
+        self.rows.push(cells.into_iter().collect());
+    }
+
+    /// Marks the cell to keep in view when the frame is too big to show
+    /// whole, such as a falling rock or the head of a rope.
+    pub fn with_focus(mut self, focus: Point<usize>) -> Self {
+        self.focus = Some(focus);
+        self
+    }
+
+    pub fn rows(&self) -> &[Vec<(char, Colour)>] {
+        &self.rows
+    }
+
+    pub fn caption(&self) -> &str {
+        &self.caption
+    }
+
+    pub fn focus(&self) -> Option<Point<usize>> {
+        self.focus
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// The picture without its colours, a line per row.
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for row in &self.rows {
+            text.extend(row.iter().map(|&(ch, _)| ch));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+/// A simulation that can be watched a step at a time.
+pub trait Animate {
+    /// The frames of solving `part`, from the parsed input onwards. They are
+    /// made as they're asked for, so a long simulation costs nothing until
+    /// it's watched.
+    fn frames(&self, part: u8) -> Box<dyn Iterator<Item = Frame> + '_>;
+}
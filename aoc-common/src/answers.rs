@@ -0,0 +1,134 @@
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::Answer;
+
+// day -> input path -> part -> answer
+type Table = BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>;
+
+/// How a computed answer compares to the one on record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Fail { expected: String },
+    Unknown,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "PASS"),
+            Status::Fail { expected } => write!(f, "FAIL, expected {}", expected),
+            Status::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+/// Known-correct answers, kept in `answers.toml` at the top of the workspace
+/// and keyed by day, input file and part.
+#[derive(Debug, Default)]
+pub struct Answers {
+    path: PathBuf,
+    table: Table,
+    changed: bool,
+}
+
+impl Answers {
+    pub fn workspace_file() -> PathBuf {
+        workspace_root().join("answers.toml")
+    }
+
+    /// Loads the answers file; a file that doesn't exist yet holds no answers.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let table = match fs::read_to_string(path) {
+            Ok(contents) => toml::from_str(&contents).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{}: {}", path.display(), err),
+                )
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Table::new(),
+            Err(err) => return Err(err),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            table,
+            changed: false,
+        })
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.table
+            .get(&format!("day{}", day))?
+            .get(input)?
+            .get(&format!("part{}", part))
+            .map(String::as_str)
+    }
+
+    pub fn check(&self, day: u8, input: &str, part: u8, answer: &Answer) -> Status {
+        match self.get(day, input, part) {
+            Some(expected) if expected == answer.to_string() => Status::Pass,
+            Some(expected) => Status::Fail {
+                expected: expected.to_string(),
+            },
+            None => Status::Unknown,
+        }
+    }
+
+    /// Records an answer that isn't known yet. Known answers are never
+    /// replaced, a wrong one has to be fixed by hand.
+    pub fn record(&mut self, day: u8, input: &str, part: u8, answer: &Answer) -> bool {
+        let parts = self
+            .table
+            .entry(format!("day{}", day))
+            .or_default()
+            .entry(input.to_string())
+            .or_default();
+        let part = format!("part{}", part);
+        if parts.contains_key(&part) {
+            return false;
+        }
+        parts.insert(part, answer.to_string());
+        self.changed = true;
+        true
+    }
+
+    /// Writes the file back if anything was recorded.
+    pub fn save(&self) -> io::Result<()> {
+        if !self.changed {
+            return Ok(());
+        }
+        let contents = toml::to_string(&self.table)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        fs::write(&self.path, contents)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .unwrap()
+        .to_path_buf()
+}
+
+/// Names an input file the same way wherever it's run from: relative to the
+/// workspace when it's inside it, e.g. "day1/input.txt".
+pub fn input_key(path: &Path) -> String {
+    let root = workspace_root()
+        .canonicalize()
+        .unwrap_or_else(|_| workspace_root());
+    match path.canonicalize() {
+        Ok(path) => match path.strip_prefix(&root) {
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => path.display().to_string(),
+        },
+        Err(_) => path.display().to_string(),
+    }
+}
@@ -0,0 +1,26 @@
+use std::{fs, path::Path};
+
+use criterion::{black_box, Criterion};
+
+use crate::Solution;
+
+/// Times parsing and each part separately for every input in `paths`,
+/// relative to the day's crate. Inputs that are not checked in are skipped.
+pub fn bench<T: Solution>(c: &mut Criterion, day: &str, paths: &[&str]) {
+    for path in paths {
+        if !Path::new(path).exists() {
+            eprintln!("skipping {}: not found", path);
+            continue;
+        }
+        let input = fs::read_to_string(path)
+            .unwrap_or_else(|err| panic!("could not read {}: {}", path, err));
+        let puzzle = T::parse(&input).unwrap_or_else(|err| panic!("{}: {}", path, err));
+        let name = path.trim_end_matches(".txt");
+
+        let mut group = c.benchmark_group(format!("{}/{}", day, name));
+        group.bench_function("parse", |b| b.iter(|| T::parse(black_box(&input))));
+        group.bench_function("part1", |b| b.iter(|| puzzle.part1()));
+        group.bench_function("part2", |b| b.iter(|| puzzle.part2()));
+        group.finish();
+    }
+}
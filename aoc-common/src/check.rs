@@ -0,0 +1,37 @@
+use std::{fs, path::Path};
+
+use crate::{Answer, Generate, Rng, Solution};
+
+/// Solves both parts for the input at `path`, relative to the day's crate,
+/// and asserts the known answers.
+pub fn check<T: Solution>(path: &str, part1: impl Into<Answer>, part2: impl Into<Answer>) {
+    let input =
+        fs::read_to_string(path).unwrap_or_else(|err| panic!("could not read {}: {}", path, err));
+    let puzzle = T::parse(&input).unwrap_or_else(|err| panic!("{}: {}", path, err));
+
+    assert_eq!(puzzle.part1(), part1.into(), "{} part 1", path);
+    assert_eq!(puzzle.part2(), part2.into(), "{} part 2", path);
+}
+
+/// Like `check`, for personal puzzle inputs. Those are not everyone's to
+/// share, so a missing file is skipped rather than failed.
+pub fn check_input<T: Solution>(path: &str, part1: impl Into<Answer>, part2: impl Into<Answer>) {
+    if !Path::new(path).exists() {
+        eprintln!("skipping {}: not found", path);
+        return;
+    }
+    check::<T>(path, part1, part2);
+}
+
+/// Makes up inputs of `size` from the first `seeds` seeds and checks they
+/// parse and solve, so a generator can't drift away from what its day
+/// accepts.
+pub fn check_generated<T: Solution + Generate>(size: usize, seeds: u64) {
+    for seed in 0..seeds {
+        let input = T::generate(size, &mut Rng::new(seed));
+        let puzzle =
+            T::parse(&input).unwrap_or_else(|err| panic!("seed {}: {}\n{}", seed, err, input));
+        puzzle.part1();
+        puzzle.part2();
+    }
+}
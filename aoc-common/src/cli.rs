@@ -0,0 +1,241 @@
+use std::{
+    env, process,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use tracing::info_span;
+
+use crate::{
+    answers::{input_key, Answers, Status},
+    init_logging, parse_or_exit, Answer, Input, Solution,
+};
+
+/// How answers are printed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Each answer with its day, part and verification status
+    #[default]
+    Text,
+    /// Only the answers, one per line
+    Plain,
+    /// One JSON record per answer: `{day, part, answer, elapsed_ms}`
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "plain" => Ok(Format::Plain),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, plain or json",
+                s
+            )),
+        }
+    }
+}
+
+/// One part's answer and how long solving it took.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solved {
+    pub part: u8,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Solves one part of a parsed puzzle, timing it.
+pub fn solve(puzzle: &dyn Solution, part: u8) -> Solved {
+    let _span = info_span!("part", part).entered();
+    let start = Instant::now();
+    let answer = match part {
+        1 => puzzle.part1(),
+        _ => puzzle.part2(),
+    };
+    Solved {
+        part,
+        answer,
+        elapsed: start.elapsed(),
+    }
+}
+
+// Answers are only kept for input files, named by where they are
+fn lookup(input: &Input) -> Option<(String, Answers)> {
+    let Input::File(path) = input else {
+        return None;
+    };
+    match Answers::load(&Answers::workspace_file()) {
+        Ok(answers) => Some((input_key(path), answers)),
+        Err(err) => {
+            eprintln!("Could not load answers: {}", err);
+            None
+        }
+    }
+}
+
+/// Checks each part's answer against the answers file.
+pub fn verify(day: u8, input: &Input, results: &[Solved]) -> Vec<Status> {
+    match lookup(input) {
+        Some((key, answers)) => results
+            .iter()
+            .map(|solved| answers.check(day, &key, solved.part, &solved.answer))
+            .collect(),
+        None => vec![Status::Unknown; results.len()],
+    }
+}
+
+/// Saves the answers that aren't in the answers file yet.
+pub fn record_answers(day: u8, input: &Input, results: &[Solved]) {
+    let Some((key, mut answers)) = lookup(input) else {
+        eprintln!("Only answers for input files can be recorded");
+        return;
+    };
+    let recorded = results
+        .iter()
+        .filter(|solved| answers.record(day, &key, solved.part, &solved.answer))
+        .count();
+    match answers.save() {
+        Ok(()) => eprintln!(
+            "Recorded {} new answer(s) in {}",
+            recorded,
+            answers.path().display()
+        ),
+        Err(err) => eprintln!("Could not save answers: {}", err),
+    }
+}
+
+// A line of `--format json` output
+#[derive(Serialize)]
+struct Record {
+    day: u8,
+    part: u8,
+    answer: Value,
+    elapsed_ms: f64,
+}
+
+/// Prints the answers in the given format. Only text shows the statuses, the
+/// other formats report wrong answers on stderr to keep stdout parseable.
+pub fn print_results(day: u8, format: Format, results: &[Solved], statuses: &[Status]) {
+    for (solved, status) in results.iter().zip(statuses) {
+        match format {
+            Format::Text => println!(
+                "Day {} part {}: {} [{}]",
+                day, solved.part, solved.answer, status
+            ),
+            Format::Plain => println!("{}", solved.answer),
+            Format::Json => {
+                let record = Record {
+                    day,
+                    part: solved.part,
+                    answer: match &solved.answer {
+                        Answer::Num(num) => json!(num),
+                        Answer::Text(text) => json!(text),
+                        Answer::None => Value::Null,
+                    },
+                    elapsed_ms: solved.elapsed.as_secs_f64() * 1000.0,
+                };
+                println!("{}", serde_json::to_string(&record).unwrap());
+            }
+        }
+        if format != Format::Text {
+            if let Status::Fail { .. } = status {
+                eprintln!("Day {} part {}: {}", day, solved.part, status);
+            }
+        }
+    }
+}
+
+struct Options {
+    input: Input,
+    format: Format,
+    record: bool,
+    verbosity: u8,
+}
+
+// The options for solving, then the arguments of any other modes
+fn usage(program: &str, modes: &[&str]) -> String {
+    let mut usage = format!(
+        "usage: {} [--format text|plain|json] [-v | -vv] [--record] <input file | ->",
+        program
+    );
+    for mode in modes {
+        usage += &format!("\n       {} {}", program, mode);
+    }
+    usage
+}
+
+fn options(mut args: impl Iterator<Item = String>, modes: &[&str]) -> Result<Options, String> {
+    let program = args.next().unwrap_or_else(|| "aoc".to_string());
+    let mut input = None;
+    let mut format = Format::default();
+    let mut record = false;
+    let mut verbosity = 0;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--record" => record = true,
+            "-v" | "--verbose" => verbosity += 1,
+            "-vv" => verbosity += 2,
+            "--format" => {
+                let value = args.next().ok_or_else(|| usage(&program, modes))?;
+                format = value.parse()?;
+            }
+            _ if arg.starts_with("--") => return Err(usage(&program, modes)),
+            _ if input.is_none() => input = Some(Input::from(arg.as_str())),
+            _ => return Err(usage(&program, modes)),
+        }
+    }
+    Ok(Options {
+        input: input.ok_or_else(|| usage(&program, modes))?,
+        format,
+        record,
+        verbosity,
+    })
+}
+
+/// Entry point for the day binaries: `dayN [options] <input file | ->`.
+/// Solves both parts and reports each against the answers file, exiting
+/// with an error if any is wrong.
+pub fn run<T: Solution>(day: u8) {
+    run_with_modes::<T>(day, &[]);
+}
+
+/// Like `run`, for day binaries that do more than solve. `modes` are the
+/// arguments each other mode takes, listed with the options for solving
+/// when the arguments are wrong, e.g. `--stream [--top <k>] <input file | ->`.
+pub fn run_with_modes<T: Solution>(day: u8, modes: &[&str]) {
+    let options = match options(env::args(), modes) {
+        Ok(options) => options,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(2);
+        }
+    };
+    let contents = match options.input.clone().read() {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    };
+    init_logging(options.verbosity);
+    let _span = info_span!("day", day).entered();
+    let puzzle: T = parse_or_exit(&contents);
+
+    let results = [solve(&puzzle, 1), solve(&puzzle, 2)];
+    let statuses = verify(day, &options.input, &results);
+    print_results(day, options.format, &results, &statuses);
+    if options.record {
+        record_answers(day, &options.input, &results);
+    }
+    if statuses
+        .iter()
+        .any(|status| matches!(status, Status::Fail { .. }))
+    {
+        process::exit(1);
+    }
+}
@@ -0,0 +1,99 @@
+use std::io::{self, Write};
+
+use crate::{Image, Rgb};
+
+impl Image {
+    /// Writes the image as a PNG.
+    pub fn write_png(&self, mut out: impl Write) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(&mut out, self.width() as u32, self.height() as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels().concat())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)?;
+        out.flush()
+    }
+}
+
+/// An animated GIF being written a frame at a time, so a long simulation
+/// never has to be held in memory whole. The GIF loops forever.
+pub struct Gif<W: Write> {
+    encoder: gif::Encoder<W>,
+    width: u16,
+    height: u16,
+    delay: u16,
+}
+
+fn dimension(size: usize) -> io::Result<u16> {
+    u16::try_from(size).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} pixels is too big for a GIF", size),
+        )
+    })
+}
+
+impl<W: Write> Gif<W> {
+    /// Starts a GIF `width` by `height` pixels whose frames each show for
+    /// `delay` hundredths of a second.
+    pub fn new(out: W, width: usize, height: usize, delay: u16) -> io::Result<Self> {
+        let (width, height) = (dimension(width)?, dimension(height)?);
+        let mut encoder = gif::Encoder::new(out, width, height, &[]).map_err(io::Error::other)?;
+        encoder
+            .set_repeat(gif::Repeat::Infinite)
+            .map_err(io::Error::other)?;
+        Ok(Self {
+            encoder,
+            width,
+            height,
+            delay,
+        })
+    }
+
+    /// Adds a frame, centred if it's smaller than the GIF. Frames drawn from
+    /// a few colours keep them exactly, anything more is quantized.
+    pub fn add(&mut self, image: &Image) -> io::Result<()> {
+        let image = image.centred(self.width as usize, self.height as usize);
+        if image.width() > self.width as usize || image.height() > self.height as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "frame is bigger than the GIF",
+            ));
+        }
+        let mut palette: Vec<Rgb> = Vec::new();
+        let mut indices = Vec::with_capacity(image.pixels().len());
+        for &pixel in image.pixels() {
+            let index = match palette.iter().position(|&colour| colour == pixel) {
+                Some(index) => index,
+                None => {
+                    palette.push(pixel);
+                    palette.len() - 1
+                }
+            };
+            if palette.len() > 256 {
+                break;
+            }
+            indices.push(index as u8);
+        }
+        let mut frame = if palette.len() <= 256 {
+            gif::Frame::from_palette_pixels(
+                self.width,
+                self.height,
+                indices,
+                palette.concat(),
+                None,
+            )
+        } else {
+            gif::Frame::from_rgb_speed(self.width, self.height, &image.pixels().concat(), 10)
+        };
+        frame.delay = self.delay;
+        self.encoder.write_frame(&frame).map_err(io::Error::other)
+    }
+
+    /// Ends the GIF, handing back what it was written to.
+    pub fn finish(self) -> io::Result<W> {
+        self.encoder.into_inner()
+    }
+}
@@ -0,0 +1,59 @@
+use std::ops::Range;
+
+/// A small seeded random number generator (SplitMix64) for making up
+/// inputs. What it gives depends on nothing but the seed, so an input can
+/// always be made again from its seed.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number in `range`, which mustn't be empty.
+    pub fn range(&mut self, range: Range<i64>) -> i64 {
+        assert!(!range.is_empty(), "empty range {:?}", range);
+        let span = range.end.abs_diff(range.start);
+        range.start.wrapping_add((self.next_u64() % span) as i64)
+    }
+
+    /// An index below `len`, which mustn't be 0.
+    pub fn below(&mut self, len: usize) -> usize {
+        self.range(0..len as i64) as usize
+    }
+
+    /// True `percent` times in a hundred.
+    pub fn chance(&mut self, percent: u64) -> bool {
+        self.next_u64() % 100 < percent
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+/// A puzzle that can make up its own inputs, to find out how it copes with
+/// sizes the real inputs never reach.
+pub trait Generate {
+    /// A valid input made from `rng`, as big as `size` says. What size
+    /// counts is up to the day: elves for day 1, the side of the forest for
+    /// day 8 and so on.
+    fn generate(size: usize, rng: &mut Rng) -> String;
+}
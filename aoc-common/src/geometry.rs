@@ -0,0 +1,311 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A point or vector on a 2D map. The days count y downwards, the way the
+/// puzzle maps read, so `Dir4::Up` takes y towards 0.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+/// A point or vector in 3D space.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+// |a - b| that can't underflow for unsigned coordinates
+fn diff<T: Copy + Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point<T> {
+    /// Distance moving only across and down, the "taxicab" distance.
+    pub fn manhattan(self, other: Self) -> T {
+        diff(self.x, other.x) + diff(self.y, other.y)
+    }
+
+    /// Distance moving diagonally as well, so the number of king's moves.
+    pub fn chebyshev(self, other: Self) -> T {
+        diff(self.x, other.x).max(diff(self.y, other.y))
+    }
+}
+
+impl<T: Copy + TryFrom<i64> + TryInto<i64>> Point<T> {
+    /// Takes one step in `dir`, or None if that leaves the range of `T`,
+    /// such as an unsigned point stepping off the top or left edge.
+    pub fn step(self, dir: Dir4) -> Option<Self> {
+        self.offset(dir.offset())
+    }
+
+    /// Moves by a signed vector, or None if the result doesn't fit in `T`.
+    pub fn offset(self, by: Point<i64>) -> Option<Self> {
+        let x: i64 = self.x.try_into().ok()?;
+        let y: i64 = self.y.try_into().ok()?;
+        Some(Self::new(
+            x.checked_add(by.x)?.try_into().ok()?,
+            y.checked_add(by.y)?.try_into().ok()?,
+        ))
+    }
+}
+
+impl<T: Copy> Point<T> {
+    /// Converts to another coordinate type, or None if either coordinate
+    /// doesn't fit, e.g. a negative point into `Point<usize>`.
+    pub fn convert<U: TryFrom<T>>(self) -> Option<Point<U>> {
+        Some(Point::new(self.x.try_into().ok()?, self.y.try_into().ok()?))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point<T> {
+    /// The four orthogonal neighbours, clockwise from up.
+    pub fn neighbours4(self) -> [Self; 4] {
+        Dir4::ALL.map(|dir| self + dir.offset())
+    }
+
+    /// All eight neighbours, clockwise from up.
+    pub fn neighbours8(self) -> [Self; 8] {
+        Dir8::ALL.map(|dir| self + dir.offset())
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Self::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(pt: Point<T>) -> Self {
+        (pt.x, pt.y)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, scale: T) -> Self {
+        Self::new(self.x * scale, self.y * scale)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl<T> Point3<T> {
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T>> Point3<T> {
+    pub fn manhattan(self, other: Self) -> T {
+        diff(self.x, other.x) + diff(self.y, other.y) + diff(self.z, other.z)
+    }
+
+    pub fn chebyshev(self, other: Self) -> T {
+        diff(self.x, other.x)
+            .max(diff(self.y, other.y))
+            .max(diff(self.z, other.z))
+    }
+}
+
+impl<T: Copy + Add<Output = T> + From<i8>> Point3<T> {
+    /// The six neighbours sharing a face.
+    pub fn neighbours6(self) -> [Self; 6] {
+        [
+            (-1, 0, 0),
+            (1, 0, 0),
+            (0, -1, 0),
+            (0, 1, 0),
+            (0, 0, -1),
+            (0, 0, 1),
+        ]
+        .map(|(x, y, z)| self + Point3::new(T::from(x), T::from(y), T::from(z)))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point3<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point3<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+/// One of the four orthogonal directions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    /// Clockwise from up.
+    pub const ALL: [Dir4; 4] = [Dir4::Up, Dir4::Right, Dir4::Down, Dir4::Left];
+
+    pub fn turn_left(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Left,
+            Dir4::Left => Dir4::Down,
+            Dir4::Down => Dir4::Right,
+            Dir4::Right => Dir4::Up,
+        }
+    }
+
+    pub fn turn_right(self) -> Self {
+        match self {
+            Dir4::Up => Dir4::Right,
+            Dir4::Right => Dir4::Down,
+            Dir4::Down => Dir4::Left,
+            Dir4::Left => Dir4::Up,
+        }
+    }
+
+    pub fn reverse(self) -> Self {
+        self.turn_left().turn_left()
+    }
+
+    /// The vector for one step this way, with y counting downwards.
+    pub fn offset<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Dir4::Up => (0, -1),
+            Dir4::Right => (1, 0),
+            Dir4::Down => (0, 1),
+            Dir4::Left => (-1, 0),
+        };
+        Point::new(T::from(x), T::from(y))
+    }
+}
+
+/// One of the eight compass directions, with north up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Dir8 {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Dir8 {
+    /// Clockwise from north.
+    pub const ALL: [Dir8; 8] = [
+        Dir8::N,
+        Dir8::NE,
+        Dir8::E,
+        Dir8::SE,
+        Dir8::S,
+        Dir8::SW,
+        Dir8::W,
+        Dir8::NW,
+    ];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Turns 45 degrees anticlockwise.
+    pub fn turn_left(self) -> Self {
+        Dir8::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turns 45 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        Dir8::ALL[(self.index() + 1) % 8]
+    }
+
+    pub fn reverse(self) -> Self {
+        Dir8::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+
+    /// The vector for one step this way, with y counting downwards.
+    pub fn offset<T: From<i8>>(self) -> Point<T> {
+        let (x, y) = match self {
+            Dir8::N => (0, -1),
+            Dir8::NE => (1, -1),
+            Dir8::E => (1, 0),
+            Dir8::SE => (1, 1),
+            Dir8::S => (0, 1),
+            Dir8::SW => (-1, 1),
+            Dir8::W => (-1, 0),
+            Dir8::NW => (-1, -1),
+        };
+        Point::new(T::from(x), T::from(y))
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        match dir {
+            Dir4::Up => Dir8::N,
+            Dir4::Right => Dir8::E,
+            Dir4::Down => Dir8::S,
+            Dir4::Left => Dir8::W,
+        }
+    }
+}
@@ -0,0 +1,286 @@
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use crate::{Dir4, Dir8, ParseError, Point};
+
+type Pos = Point<usize>;
+
+/// A rectangular grid stored row by row. Cells are addressed by a point with
+/// (0, 0) at the top left and y growing downwards, the way puzzle maps read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a map with one character per cell. `cell` turns a character
+    /// into its value, or None if it isn't allowed there, which is reported
+    /// as `expected`. Every row has to be as wide as the first.
+    pub fn parse<F>(input: &str, expected: &str, mut cell: F) -> Result<Self, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+
+        for line in input.lines() {
+            let start = cells.len();
+            for (pos, ch) in line.char_indices() {
+                match cell(ch) {
+                    Some(value) => cells.push(value),
+                    None => return Err(ParseError::at(input, &line[pos..], expected)),
+                }
+            }
+            let row_width = cells.len() - start;
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    let expected = format!("a row {} wide", width);
+                    return Err(ParseError::at(input, line, expected));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// Adds a row below the last, for grids that grow like day 17's tower.
+    /// The row has to be as wide as the grid.
+    pub fn push_row(&mut self, row: impl IntoIterator<Item = T>) {
+        let start = self.cells.len();
+        self.cells.extend(row);
+        assert_eq!(
+            self.cells.len() - start,
+            self.width,
+            "a row has to be as wide as the grid"
+        );
+        self.height += 1;
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.x < self.width && pos.y < self.height
+    }
+
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.y * self.width + pos.x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {} is off the grid", x);
+        self.cells[x..].iter().step_by(self.width.max(1))
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every position on the grid, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| Point::new(x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The first position, row by row, whose cell matches.
+    pub fn position<P>(&self, mut predicate: P) -> Option<Pos>
+    where
+        P: FnMut(&T) -> bool,
+    {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// The orthogonal neighbours of `pos` that are on the grid.
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| pos.step(dir))
+            .filter(|&next| self.contains(next))
+    }
+
+    /// The orthogonal and diagonal neighbours of `pos` that are on the grid.
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| pos.offset(dir.offset()))
+            .filter(|&next| self.contains(next))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Draws the grid with one character per cell, a line per row.
+    pub fn render<F>(&self, mut cell: F) -> String
+    where
+        F: FnMut(&T) -> char,
+    {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows().take(self.height) {
+            text.extend(row.iter().map(&mut cell));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        assert!(self.contains(pos), "{:?} is off the grid", pos);
+        &self.cells[pos.y * self.width + pos.x]
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        assert!(self.contains(pos), "{:?} is off the grid", pos);
+        &mut self.cells[pos.y * self.width + pos.x]
+    }
+}
+
+/// A grid without fixed edges that only stores the cells it has been given,
+/// for maps that spread out (day 23's elves) or are mostly empty (day 14's
+/// cave). Coordinates can go negative.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point<i64>, T>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+        }
+    }
+
+    pub fn insert(&mut self, pos: Point<i64>, value: T) -> Option<T> {
+        self.cells.insert(pos, value)
+    }
+
+    pub fn remove(&mut self, pos: Point<i64>) -> Option<T> {
+        self.cells.remove(&pos)
+    }
+
+    pub fn get(&self, pos: Point<i64>) -> Option<&T> {
+        self.cells.get(&pos)
+    }
+
+    pub fn contains(&self, pos: Point<i64>) -> bool {
+        self.cells.contains_key(&pos)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Point<i64>> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point<i64>, &T)> {
+        self.cells.iter().map(|(pos, value)| (*pos, value))
+    }
+
+    /// The smallest rectangle holding every cell, as its top left and bottom
+    /// right corners, or None when the grid is empty.
+    pub fn bounds(&self) -> Option<(Point<i64>, Point<i64>)> {
+        let mut positions = self.positions();
+        let first = positions.next()?;
+        Some(positions.fold((first, first), |(min, max), pos| {
+            (
+                Point::new(min.x.min(pos.x), min.y.min(pos.y)),
+                Point::new(max.x.max(pos.x), max.y.max(pos.y)),
+            )
+        }))
+    }
+
+    /// Draws the cells within `bounds`, a line per row, with `cell` given
+    /// None for positions that aren't set.
+    pub fn render<F>(&self, (min, max): (Point<i64>, Point<i64>), mut cell: F) -> String
+    where
+        F: FnMut(Option<&T>) -> char,
+    {
+        let mut text = String::new();
+        for y in min.y..=max.y {
+            text.extend((min.x..=max.x).map(|x| cell(self.get(Point::new(x, y)))));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> FromIterator<(Point<i64>, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point<i64>, T)>>(iter: I) -> Self {
+        Self {
+            cells: iter.into_iter().collect(),
+        }
+    }
+}
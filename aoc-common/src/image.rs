@@ -0,0 +1,164 @@
+use std::{
+    io::{self, Write},
+    ops::{Index, IndexMut},
+};
+
+use crate::{Colour, Frame, Grid, Point};
+
+/// A colour as red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// What blank cells of a frame are drawn in, the dark blue of the puzzle
+/// pages.
+pub const BACKGROUND: Rgb = [15, 15, 35];
+
+impl Colour {
+    /// The colour a frame's cell is drawn in when saved as an image.
+    pub fn rgb(self) -> Rgb {
+        match self {
+            Colour::Plain => [204, 204, 204],
+            Colour::Grey => [80, 80, 96],
+            Colour::Red => [220, 50, 47],
+            Colour::Green => [0, 204, 0],
+            Colour::Yellow => [255, 255, 102],
+            Colour::Blue => [38, 139, 210],
+            Colour::Magenta => [211, 54, 130],
+            Colour::Cyan => [42, 161, 152],
+            Colour::White => [255, 255, 255],
+        }
+    }
+}
+
+/// Where `value` falls between 0 and `max` as a colour, from dark blue for
+/// nothing through red to yellow for the most.
+pub fn heat(value: f64, max: f64) -> Rgb {
+    let t = if max > 0.0 {
+        (value / max).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let channel = |from: u8, to: u8, t: f64| (from as f64 + (to as f64 - from as f64) * t) as u8;
+    let (from, to, t) = if t < 0.5 {
+        ([20, 20, 80], [200, 40, 40], t * 2.0)
+    } else {
+        ([200, 40, 40], [255, 240, 100], t * 2.0 - 1.0)
+    };
+    [
+        channel(from[0], to[0], t),
+        channel(from[1], to[1], t),
+        channel(from[2], to[2], t),
+    ]
+}
+
+/// A picture of a puzzle's state, a pixel per cell until it's scaled up
+/// for saving.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    pub fn new(width: usize, height: usize, fill: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            pixels: vec![fill; width * height],
+        }
+    }
+
+    /// Colours each cell of a dense grid.
+    pub fn from_grid<T, F>(grid: &Grid<T>, mut pixel: F) -> Self
+    where
+        F: FnMut(&T) -> Rgb,
+    {
+        let mut image = Self::new(grid.width(), grid.height(), BACKGROUND);
+        for (pt, cell) in grid.iter() {
+            image[pt] = pixel(cell);
+        }
+        image
+    }
+
+    /// Draws a frame a pixel per cell in its cell's colour, with spaces and
+    /// short rows left as the background.
+    pub fn from_frame(frame: &Frame) -> Self {
+        let mut image = Self::new(frame.width(), frame.height(), BACKGROUND);
+        for (y, row) in frame.rows().iter().enumerate() {
+            for (x, &(ch, colour)) in row.iter().enumerate() {
+                if ch != ' ' {
+                    image[Point::new(x, y)] = colour.rgb();
+                }
+            }
+        }
+        image
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pixels a row at a time, top left first.
+    pub fn pixels(&self) -> &[Rgb] {
+        &self.pixels
+    }
+
+    /// Blows every pixel up into a `factor` by `factor` square, as single
+    /// pixels are too small to see.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let factor = factor.max(1);
+        let mut image = Self::new(self.width * factor, self.height * factor, BACKGROUND);
+        for y in 0..image.height {
+            for x in 0..image.width {
+                image[Point::new(x, y)] = self[Point::new(x / factor, y / factor)];
+            }
+        }
+        image
+    }
+
+    /// Copies the image into the middle of a bigger one, so frames of
+    /// different sizes line up.
+    pub fn centred(&self, width: usize, height: usize) -> Self {
+        let mut image = Self::new(width.max(self.width), height.max(self.height), BACKGROUND);
+        let left = (image.width - self.width) / 2;
+        let top = (image.height - self.height) / 2;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                image[Point::new(left + x, top + y)] = self[Point::new(x, y)];
+            }
+        }
+        image
+    }
+
+    /// Writes the image as a binary PPM, which needs no encoder to write
+    /// and most image tools can read.
+    pub fn write_ppm(&self, mut out: impl Write) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels.concat())?;
+        out.flush()
+    }
+}
+
+impl Index<Point<usize>> for Image {
+    type Output = Rgb;
+
+    fn index(&self, pt: Point<usize>) -> &Rgb {
+        &self.pixels[pt.y * self.width + pt.x]
+    }
+}
+
+impl IndexMut<Point<usize>> for Image {
+    fn index_mut(&mut self, pt: Point<usize>) -> &mut Rgb {
+        &mut self.pixels[pt.y * self.width + pt.x]
+    }
+}
+
+/// A puzzle whose state can be saved as a picture.
+pub trait Draw {
+    /// A picture of the puzzle once `part` is solved.
+    fn image(&self, part: u8) -> Image;
+}
@@ -0,0 +1,116 @@
+use crate::Solution;
+use std::{
+    fmt,
+    io::{self, Read},
+    path::PathBuf,
+    process,
+};
+
+/// Where a puzzle input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Io { source: String, error: io::Error },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputError::Io { source, error } => write!(f, "could not read {}: {}", source, error),
+        }
+    }
+}
+
+impl std::error::Error for InputError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            InputError::Io { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<&str> for Input {
+    // "-" reads standard input, anything else is a file path
+    fn from(arg: &str) -> Self {
+        if arg == "-" {
+            Input::Stdin
+        } else {
+            Input::File(PathBuf::from(arg))
+        }
+    }
+}
+
+impl From<PathBuf> for Input {
+    fn from(path: PathBuf) -> Self {
+        if path.as_os_str() == "-" {
+            Input::Stdin
+        } else {
+            Input::File(path)
+        }
+    }
+}
+
+impl Input {
+    pub fn read(self) -> Result<String, InputError> {
+        match self {
+            Input::File(path) => std::fs::read_to_string(&path).map_err(|error| InputError::Io {
+                source: path.display().to_string(),
+                error,
+            }),
+            Input::Stdin => {
+                let mut contents = String::new();
+                match io::stdin().read_to_string(&mut contents) {
+                    Ok(_) => Ok(contents),
+                    Err(error) => Err(InputError::Io {
+                        source: "stdin".to_string(),
+                        error,
+                    }),
+                }
+            }
+            Input::Text(text) => Ok(text),
+        }
+    }
+}
+
+/// Splits input into the groups of lines separated by blank lines. Each block
+/// keeps its inner newlines but not the trailing one.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut pos = 0;
+
+    for line in input.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                blocks.push(&input[start..end]);
+            }
+        } else {
+            start.get_or_insert(pos);
+            end = pos + line.trim_end_matches(['\n', '\r']).len();
+        }
+        pos += line.len();
+    }
+    if let Some(start) = start {
+        blocks.push(&input[start..end]);
+    }
+    blocks
+}
+
+/// Parses a day's input, printing where it is malformed and exiting if it
+/// can't be parsed.
+pub fn parse_or_exit<T: Solution>(input: &str) -> T {
+    match T::parse(input) {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            eprintln!("error: {}", err);
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+mod animate;
+mod answers;
+#[cfg(feature = "bench")]
+mod bench;
+mod check;
+mod cli;
+#[cfg(feature = "export")]
+mod export;
+mod generate;
+mod geometry;
+mod grid;
+mod image;
+mod input;
+mod log;
+mod parse;
+#[cfg(feature = "visualise")]
+mod play;
+mod search;
+mod solution;
+
+pub use animate::{Animate, Colour, Frame};
+pub use answers::{input_key, Answers, Status};
+#[cfg(feature = "bench")]
+pub use bench::bench;
+pub use check::{check, check_generated, check_input};
+pub use cli::{print_results, record_answers, run, run_with_modes, solve, verify, Format, Solved};
+#[cfg(feature = "export")]
+pub use export::Gif;
+pub use generate::{Generate, Rng};
+pub use geometry::{Dir4, Dir8, Point, Point3};
+pub use grid::{Grid, SparseGrid};
+pub use image::{heat, Draw, Image, Rgb, BACKGROUND};
+pub use input::{blocks, parse_or_exit, Input, InputError};
+pub use log::init_logging;
+pub use parse::{number, parse_lines, ParseError};
+#[cfg(feature = "visualise")]
+pub use play::play;
+pub use search::{astar, bfs, bfs_reach, dijkstra, dijkstra_reach, Path, Reached};
+pub use solution::{Answer, Solution};
@@ -0,0 +1,19 @@
+use std::io;
+
+use tracing_subscriber::EnvFilter;
+
+/// Sends the days' tracing output to stderr. Each `-v` turns on a level
+/// more (debug, then trace); without any, `RUST_LOG` picks what is shown and
+/// nothing is by default.
+pub fn init_logging(verbosity: u8) {
+    let filter = match verbosity {
+        0 => EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("off")),
+        1 => EnvFilter::new("debug"),
+        _ => EnvFilter::new("trace"),
+    };
+    tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .without_time()
+        .init();
+}
@@ -0,0 +1,94 @@
+use std::{fmt, str::FromStr};
+
+/// Where and why a puzzle input could not be parsed. Lines and columns count
+/// from 1, and `text` holds the whole offending line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    /// Error at `rest`, which must be a slice of `input` (or empty, for
+    /// running out of input).
+    pub fn at(input: &str, rest: &str, expected: impl Into<String>) -> Self {
+        let offset = offset_in(input, rest);
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+        let line_end = input[offset..]
+            .find('\n')
+            .map_or(input.len(), |pos| offset + pos);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: input[line_start..line_end]
+                .trim_end_matches('\r')
+                .to_string(),
+            expected: expected.into(),
+        }
+    }
+
+    /// Error for input that stops before something it needs.
+    pub fn at_end(input: &str, expected: impl Into<String>) -> Self {
+        // Point just past the last text rather than at a trailing blank line
+        let end = input.trim_end().len();
+        ParseError::at(input, &input[end..], expected)
+    }
+
+    /// Moves an error found while parsing `inner` to its place in `outer`,
+    /// where `inner` is a slice of `outer` such as a line or block.
+    pub fn within(mut self, outer: &str, inner: &str) -> Self {
+        let start = ParseError::at(outer, inner, "");
+        if self.line == 1 {
+            self.column += start.column - 1;
+            self.text = start.text;
+        }
+        self.line += start.line - 1;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "line {}, column {}: expected {}",
+            self.line, self.column, self.expected
+        )?;
+        writeln!(f, "  {}", self.text)?;
+        write!(f, "  {:>width$}", "^", width = self.column)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+// Byte offset of a subslice, clamped so unrelated strings point at the start
+fn offset_in(input: &str, rest: &str) -> usize {
+    let start = input.as_ptr() as usize;
+    let pos = rest.as_ptr() as usize;
+    if pos >= start && pos <= start + input.len() {
+        pos - start
+    } else {
+        0
+    }
+}
+
+/// Parses a token (a slice of `input`) as a number.
+pub fn number<T: FromStr>(input: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(input, token, "a number"))
+}
+
+/// Parses each line of `input`, numbering any error by where the line sits.
+pub fn parse_lines<T, F>(input: &str, mut parse: F) -> Result<Vec<T>, ParseError>
+where
+    F: FnMut(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|err| err.within(input, line)))
+        .collect()
+}
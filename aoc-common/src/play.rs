@@ -0,0 +1,206 @@
+use std::{
+    io::{self, BufWriter, Write},
+    time::{Duration, Instant},
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    execute, queue,
+    style::{Color, Print, ResetColor, SetForegroundColor},
+    terminal::{self, Clear, ClearType},
+};
+
+use crate::{Colour, Frame};
+
+const MAX_FPS: u32 = 960;
+
+// Switches to a screen of our own for as long as frames are playing and
+// puts the terminal back however playing ends
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Screen)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(
+            io::stdout(),
+            ResetColor,
+            cursor::Show,
+            terminal::LeaveAlternateScreen
+        );
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn colour(colour: Colour) -> Color {
+    match colour {
+        Colour::Plain => Color::Reset,
+        Colour::Grey => Color::DarkGrey,
+        Colour::Red => Color::Red,
+        Colour::Green => Color::Green,
+        Colour::Yellow => Color::Yellow,
+        Colour::Blue => Color::Blue,
+        Colour::Magenta => Color::Magenta,
+        Colour::Cyan => Color::Cyan,
+        Colour::White => Color::White,
+    }
+}
+
+// Where a view `view` long starts along a frame `size` long, keeping
+// `focus` near the middle
+fn offset(size: usize, view: usize, focus: Option<usize>) -> usize {
+    match focus {
+        Some(focus) if size > view => focus.saturating_sub(view / 2).min(size - view),
+        _ => 0,
+    }
+}
+
+enum Control {
+    Next,
+    Quit,
+}
+
+struct Player<W> {
+    out: W,
+    fps: u32,
+    paused: bool,
+    finished: bool,
+}
+
+impl<W: Write> Player<W> {
+    fn draw(&mut self, frame: &Frame, number: usize) -> io::Result<()> {
+        let (columns, lines) = terminal::size()?;
+        let (columns, lines) = (columns as usize, lines as usize);
+        // The bottom line is kept for the caption
+        let view = lines.saturating_sub(1);
+        let focus = frame.focus();
+        let left = offset(frame.width(), columns, focus.map(|pt| pt.x));
+        let top = offset(frame.height(), view, focus.map(|pt| pt.y));
+
+        queue!(self.out, cursor::MoveTo(0, 0))?;
+        for (y, row) in frame.rows().iter().skip(top).take(view).enumerate() {
+            queue!(self.out, cursor::MoveTo(0, y as u16))?;
+            let cells = row.iter().skip(left).take(columns);
+            let mut run = String::new();
+            let mut run_colour = None;
+            for &(ch, cell_colour) in cells {
+                if run_colour != Some(cell_colour) {
+                    if let Some(run_colour) = run_colour {
+                        queue!(
+                            self.out,
+                            SetForegroundColor(colour(run_colour)),
+                            Print(&run)
+                        )?;
+                    }
+                    run.clear();
+                    run_colour = Some(cell_colour);
+                }
+                run.push(ch);
+            }
+            if let Some(run_colour) = run_colour {
+                queue!(
+                    self.out,
+                    SetForegroundColor(colour(run_colour)),
+                    Print(&run)
+                )?;
+            }
+            queue!(self.out, ResetColor, Clear(ClearType::UntilNewLine))?;
+        }
+        queue!(self.out, Clear(ClearType::FromCursorDown))?;
+        self.draw_status(frame, number)
+    }
+
+    fn draw_status(&mut self, frame: &Frame, number: usize) -> io::Result<()> {
+        let (columns, lines) = terminal::size()?;
+        let state = if self.finished {
+            "finished, any key quits".to_string()
+        } else if self.paused {
+            "paused, space resumes, n steps, q quits".to_string()
+        } else {
+            format!("{} fps, space pauses, +/- speed, q quits", self.fps)
+        };
+        let status = format!("{} | frame {} | {}", frame.caption(), number, state);
+        queue!(
+            self.out,
+            cursor::MoveTo(0, lines.saturating_sub(1)),
+            Print(status.chars().take(columns as usize).collect::<String>()),
+            Clear(ClearType::UntilNewLine)
+        )?;
+        self.out.flush()
+    }
+
+    // Shows a frame for its share of a second, or until it's stepped past
+    // while paused, handling keys as they come
+    fn wait(&mut self, frame: &Frame, number: usize) -> io::Result<Control> {
+        let deadline = Instant::now() + Duration::from_secs(1) / self.fps;
+        loop {
+            let ready = if self.paused || self.finished {
+                true
+            } else {
+                event::poll(deadline.saturating_duration_since(Instant::now()))?
+            };
+            if !ready {
+                return Ok(Control::Next);
+            }
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    let ctrl_c = key.code == KeyCode::Char('c')
+                        && key.modifiers.contains(KeyModifiers::CONTROL);
+                    if self.finished || ctrl_c {
+                        return Ok(Control::Quit);
+                    }
+                    match key.code {
+                        KeyCode::Char('q') | KeyCode::Esc => return Ok(Control::Quit),
+                        KeyCode::Char(' ') => self.paused = !self.paused,
+                        KeyCode::Right | KeyCode::Char('n') if self.paused => {
+                            return Ok(Control::Next)
+                        }
+                        KeyCode::Char('+') | KeyCode::Char('=') => {
+                            self.fps = (self.fps * 2).min(MAX_FPS)
+                        }
+                        KeyCode::Char('-') => self.fps = (self.fps / 2).max(1),
+                        _ => {}
+                    }
+                    self.draw_status(frame, number)?;
+                }
+                Event::Resize(..) => self.draw(frame, number)?,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Plays a simulation's frames in the terminal, `fps` of them a second.
+/// Space pauses and resumes, n or the right arrow steps a frame while
+/// paused, + and - double and halve the speed, and q or Esc stops. The last
+/// frame stays up until a key is pressed.
+pub fn play(frames: impl Iterator<Item = Frame>, fps: u32) -> io::Result<()> {
+    let _screen = Screen::enter()?;
+    let mut player = Player {
+        out: BufWriter::new(io::stdout()),
+        fps: fps.clamp(1, MAX_FPS),
+        paused: false,
+        finished: false,
+    };
+    let mut last = None;
+    for (number, frame) in (1..).zip(frames) {
+        player.draw(&frame, number)?;
+        if let Control::Quit = player.wait(&frame, number)? {
+            return Ok(());
+        }
+        last = Some((frame, number));
+    }
+    if let Some((frame, number)) = last {
+        player.finished = true;
+        player.draw_status(&frame, number)?;
+        player.wait(&frame, number)?;
+    }
+    Ok(())
+}
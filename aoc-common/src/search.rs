@@ -0,0 +1,286 @@
+use std::{
+    cmp::Ordering,
+    collections::{hash_map::Entry, BinaryHeap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use rustc_hash::FxHashMap;
+
+/// A route found by a search: the nodes from a start to the goal, both
+/// included, and what it cost to follow them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn start(&self) -> &N {
+        &self.nodes[0]
+    }
+
+    pub fn goal(&self) -> &N {
+        &self.nodes[self.nodes.len() - 1]
+    }
+}
+
+/// Every node a search reached, with the cheapest cost found to it and the
+/// node it was reached from, so the way there can be followed back.
+#[derive(Debug, Clone)]
+pub struct Reached<N, C> {
+    nodes: FxHashMap<N, (Option<N>, C)>,
+}
+
+impl<N: Clone + Eq + Hash, C: Copy> Reached<N, C> {
+    fn new() -> Self {
+        Self {
+            nodes: FxHashMap::default(),
+        }
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.nodes.contains_key(node)
+    }
+
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.nodes.get(node).map(|&(_, cost)| cost)
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.keys()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&N, C)> {
+        self.nodes.iter().map(|(node, &(_, cost))| (node, cost))
+    }
+
+    /// Follows the way back from `node` to the start it was reached from.
+    pub fn path_to(&self, node: &N) -> Option<Path<N, C>> {
+        let &(_, cost) = self.nodes.get(node)?;
+        let mut nodes = vec![node.clone()];
+        while let Some((Some(parent), _)) = self.nodes.get(&nodes[nodes.len() - 1]) {
+            nodes.push(parent.clone());
+        }
+        nodes.reverse();
+        Some(Path { nodes, cost })
+    }
+}
+
+// Breadth first from all of `starts` until a node passes `success`
+fn bfs_until<N, FN, IN, FS>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: FN,
+    mut success: FS,
+) -> (Reached<N, usize>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let mut reached = Reached::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = reached.nodes.entry(start.clone()) {
+            entry.insert((None, 0));
+            queue.push_back((start, 0));
+        }
+    }
+    while let Some((node, steps)) = queue.pop_front() {
+        if success(&node) {
+            return (reached, Some(node));
+        }
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = reached.nodes.entry(next.clone()) {
+                entry.insert((Some(node.clone()), steps + 1));
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    (reached, None)
+}
+
+/// The fewest steps from any of `starts` to a node that passes `success`,
+/// where every step costs the same.
+pub fn bfs<N, FN, IN, FS>(
+    starts: impl IntoIterator<Item = N>,
+    successors: FN,
+    success: FS,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+    FS: FnMut(&N) -> bool,
+{
+    let (reached, goal) = bfs_until(starts, successors, success);
+    reached.path_to(&goal?)
+}
+
+/// Every node that can be reached from `starts`, each with the fewest steps
+/// it takes. Nodes are expanded in order of steps, so a level is finished
+/// before the next one starts.
+pub fn bfs_reach<N, FN, IN>(
+    starts: impl IntoIterator<Item = N>,
+    successors: FN,
+) -> Reached<N, usize>
+where
+    N: Clone + Eq + Hash,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = N>,
+{
+    bfs_until(starts, successors, |_| false).0
+}
+
+// A node waiting to be expanded, ordered so the heap pops the lowest
+// estimate first
+struct Open<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Open<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<N, C: Ord> Eq for Open<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Open<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Open<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .estimate
+            .cmp(&self.estimate)
+            .then_with(|| self.cost.cmp(&other.cost))
+    }
+}
+
+// Best first from all of `starts`, guided by `heuristic`, until a node
+// passes `success`. A zero heuristic makes this Dijkstra's algorithm.
+fn best_first<N, C, FN, IN, FH, FS>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut success: FS,
+) -> (Reached<N, C>, Option<N>)
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let mut reached = Reached::new();
+    let mut open = BinaryHeap::new();
+    for start in starts {
+        if let Entry::Vacant(entry) = reached.nodes.entry(start.clone()) {
+            entry.insert((None, C::default()));
+            open.push(Open {
+                estimate: heuristic(&start),
+                cost: C::default(),
+                node: start,
+            });
+        }
+    }
+    while let Some(Open { cost, node, .. }) = open.pop() {
+        // Skip entries left behind when a cheaper way was found
+        if reached.cost(&node).is_some_and(|best| best < cost) {
+            continue;
+        }
+        if success(&node) {
+            return (reached, Some(node));
+        }
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match reached.nodes.entry(next.clone()) {
+                Entry::Occupied(mut entry) => {
+                    if next_cost >= entry.get().1 {
+                        continue;
+                    }
+                    entry.insert((Some(node.clone()), next_cost));
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert((Some(node.clone()), next_cost));
+                }
+            }
+            open.push(Open {
+                estimate: next_cost + heuristic(&next),
+                cost: next_cost,
+                node: next,
+            });
+        }
+    }
+    (reached, None)
+}
+
+/// The cheapest way from any of `starts` to a node that passes `success`,
+/// where `successors` gives each next node with the cost of moving to it.
+pub fn dijkstra<N, C, FN, IN, FS>(
+    starts: impl IntoIterator<Item = N>,
+    successors: FN,
+    success: FS,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FS: FnMut(&N) -> bool,
+{
+    let (reached, goal) = best_first(starts, successors, |_| C::default(), success);
+    reached.path_to(&goal?)
+}
+
+/// Every node that can be reached from `starts`, each with the cheapest
+/// cost to get there.
+pub fn dijkstra_reach<N, C, FN, IN>(
+    starts: impl IntoIterator<Item = N>,
+    successors: FN,
+) -> Reached<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+{
+    best_first(starts, successors, |_| C::default(), |_| false).0
+}
+
+/// Like `dijkstra`, but expands the nodes `heuristic` estimates are closest
+/// to the goal first. The heuristic must never overestimate the remaining
+/// cost or the path found may not be the cheapest.
+pub fn astar<N, C, FN, IN, FH, FS>(
+    starts: impl IntoIterator<Item = N>,
+    successors: FN,
+    heuristic: FH,
+    success: FS,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    FN: FnMut(&N) -> IN,
+    IN: IntoIterator<Item = (N, C)>,
+    FH: FnMut(&N) -> C,
+    FS: FnMut(&N) -> bool,
+{
+    let (reached, goal) = best_first(starts, successors, heuristic, success);
+    reached.path_to(&goal?)
+}
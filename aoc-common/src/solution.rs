@@ -0,0 +1,77 @@
+use std::fmt;
+
+use crate::ParseError;
+
+/// The answer to one part of a puzzle. Most days answer with a number, a
+/// few (day 5's crates, day 10's screen, day 25's SNAFU) answer with text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Num(i64),
+    Text(String),
+    /// No answer: the part has no puzzle (day 25 part 2) or no solution
+    None,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Num(num) => write!(f, "{}", num),
+            Answer::Text(text) => write!(f, "{}", text),
+            Answer::None => write!(f, "-"),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(num: $int) -> Self {
+                    Answer::Num(num.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(i32, u32, i64);
+
+// Unsigned answers too big for an i64 are kept whole as text rather than
+// wrapping round to a negative number
+macro_rules! answer_from_unsigned {
+    ($($int:ty),*) => {
+        $(
+            impl From<$int> for Answer {
+                fn from(num: $int) -> Self {
+                    i64::try_from(num).map_or_else(|_| Answer::Text(num.to_string()), Answer::Num)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_unsigned!(u64, usize);
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Answer::Text(text)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(text: &str) -> Self {
+        Answer::Text(text.to_string())
+    }
+}
+
+/// Common entry points for a day's puzzle: build it from the puzzle input,
+/// then solve either part. Solving never modifies the parsed puzzle, so both
+/// parts can be run on the same value in any order. Malformed input is
+/// reported as a `ParseError` rather than a panic.
+pub trait Solution {
+    fn parse(input: &str) -> Result<Self, ParseError>
+    where
+        Self: Sized;
+    fn part1(&self) -> Answer;
+    fn part2(&self) -> Answer;
+}
@@ -0,0 +1,25 @@
+use aoc_common::{Colour, Frame, Grid, Point, SparseGrid};
+
+#[test]
+fn frames_from_grids() {
+    let grid = Grid::parse("#.\n.#\n", "# or .", |ch| Some(ch == '#')).unwrap();
+    let frame = Frame::from_grid(&grid, "dense", |&wall| match wall {
+        true => ('#', Colour::Grey),
+        false => ('.', Colour::Plain),
+    });
+    assert_eq!(frame.text(), "#.\n.#\n");
+    assert_eq!(frame.rows()[0][0], ('#', Colour::Grey));
+    assert_eq!(frame.caption(), "dense");
+
+    let sparse: SparseGrid<char> = [(Point::new(-1, 0), 'a'), (Point::new(1, 1), 'b')]
+        .into_iter()
+        .collect();
+    let bounds = sparse.bounds().unwrap();
+    let frame = Frame::from_sparse(&sparse, bounds, "sparse", |cell| {
+        (cell.copied().unwrap_or(' '), Colour::Plain)
+    })
+    .with_focus(Point::new(2, 1));
+    assert_eq!(frame.text(), "a  \n  b\n");
+    assert_eq!((frame.width(), frame.height()), (3, 2));
+    assert_eq!(frame.focus(), Some(Point::new(2, 1)));
+}
@@ -0,0 +1,19 @@
+use aoc_common::Rng;
+
+#[test]
+fn seeded_random_numbers() {
+    let numbers = |seed| {
+        let mut rng = Rng::new(seed);
+        (0..100).map(|_| rng.range(-5..5)).collect::<Vec<_>>()
+    };
+    assert_eq!(numbers(7), numbers(7));
+    assert_ne!(numbers(7), numbers(8));
+    assert!(numbers(7).iter().all(|n| (-5..5).contains(n)));
+
+    let mut rng = Rng::new(1);
+    let mut items: Vec<u32> = (0..20).collect();
+    rng.shuffle(&mut items);
+    assert_ne!(items, (0..20).collect::<Vec<_>>());
+    items.sort();
+    assert_eq!(items, (0..20).collect::<Vec<_>>());
+}
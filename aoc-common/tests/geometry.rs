@@ -0,0 +1,43 @@
+use aoc_common::{Dir4, Dir8, Point, Point3};
+
+#[test]
+fn arithmetic_and_distances() {
+    let a = Point::new(1, -2);
+    let b = Point::new(4, 2);
+    assert_eq!(a + b, Point::new(5, 0));
+    assert_eq!(b - a, Point::new(3, 4));
+    assert_eq!(a * 2, Point::new(2, -4));
+    assert_eq!(a.manhattan(b), 7);
+    assert_eq!(a.chebyshev(b), 4);
+    // Unsigned distances don't underflow whichever way round they are
+    assert_eq!(Point::new(5_u32, 1).manhattan(Point::new(2, 3)), 5);
+    assert_eq!(Point3::new(1, 2, 3).manhattan(Point3::new(-1, 2, 5)), 4);
+}
+
+#[test]
+fn turning() {
+    assert_eq!(Dir4::Up.turn_right(), Dir4::Right);
+    assert_eq!(Dir4::Up.turn_left(), Dir4::Left);
+    assert_eq!(Dir4::Left.reverse(), Dir4::Right);
+    assert_eq!(Dir8::N.turn_left(), Dir8::NW);
+    assert_eq!(Dir8::NW.turn_right(), Dir8::N);
+    assert!(Dir8::SE.is_diagonal());
+    for dir in Dir4::ALL {
+        assert_eq!(dir.turn_left().turn_right(), dir);
+        assert_eq!(Dir8::from(dir).offset::<i32>(), dir.offset());
+    }
+}
+
+#[test]
+fn checked_steps_and_conversion() {
+    let origin = Point::new(0_usize, 0);
+    assert_eq!(origin.step(Dir4::Up), None);
+    assert_eq!(origin.step(Dir4::Down), Some(Point::new(0, 1)));
+    assert_eq!(Point::new(-1_i64, 3).convert::<usize>(), None);
+    assert_eq!(
+        Point::new(2_i64, 3).convert::<usize>(),
+        Some(Point::new(2, 3))
+    );
+    assert_eq!(Point::new(0, 0).neighbours4()[0], Point::new(0, -1));
+    assert_eq!(Point3::new(0, 0, 0).neighbours6().len(), 6);
+}
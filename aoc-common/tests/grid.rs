@@ -0,0 +1,87 @@
+use aoc_common::{Grid, Point, SparseGrid};
+
+const MAP: &str = "\
+123
+456
+";
+
+#[test]
+fn parse_and_views() {
+    let grid = Grid::parse(MAP, "a digit", |ch| ch.to_digit(10)).unwrap();
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[Point::new(2, 1)], 6);
+    assert_eq!(grid.row(1), &[4, 5, 6]);
+    assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+    assert_eq!(grid.position(|&cell| cell == 4), Some(Point::new(0, 1)));
+    assert_eq!(grid.get(Point::new(3, 0)), None);
+    assert_eq!(
+        grid.render(|&cell| char::from_digit(cell, 10).unwrap()),
+        MAP
+    );
+}
+
+#[test]
+fn push_rows() {
+    let mut grid = Grid::new(3, 0, 0);
+    grid.push_row([1, 2, 3]);
+    grid.push_row([4, 5, 6]);
+    assert_eq!(
+        grid,
+        Grid::parse(MAP, "a digit", |ch| ch.to_digit(10)).unwrap()
+    );
+}
+
+#[test]
+#[should_panic(expected = "a row has to be as wide as the grid")]
+fn push_row_too_narrow() {
+    Grid::new(3, 1, 0).push_row([1, 2]);
+}
+
+#[test]
+#[should_panic(expected = "column 3 is off the grid")]
+fn column_off_the_grid() {
+    let grid = Grid::parse(MAP, "a digit", |ch| ch.to_digit(10)).unwrap();
+    // Would otherwise start down the next row's first column
+    grid.column(3).count();
+}
+
+#[test]
+#[should_panic]
+fn row_off_the_grid() {
+    let grid = Grid::parse(MAP, "a digit", |ch| ch.to_digit(10)).unwrap();
+    grid.row(2);
+}
+
+#[test]
+fn parse_errors() {
+    let err = Grid::parse("12\n3x\n", "a digit", |ch| ch.to_digit(10)).unwrap_err();
+    assert_eq!((err.line, err.column), (2, 2));
+    let err = Grid::parse("12\n345\n", "a digit", |ch| ch.to_digit(10)).unwrap_err();
+    assert_eq!((err.line, err.expected.as_str()), (2, "a row 2 wide"));
+}
+
+#[test]
+fn neighbours_stay_on_the_grid() {
+    let grid = Grid::new(3, 3, 0);
+    assert_eq!(
+        grid.neighbours4(Point::new(0, 0)).collect::<Vec<_>>(),
+        [Point::new(1, 0), Point::new(0, 1)]
+    );
+    assert_eq!(grid.neighbours8(Point::new(1, 1)).count(), 8);
+    assert_eq!(grid.neighbours8(Point::new(2, 2)).count(), 3);
+}
+
+#[test]
+fn sparse_bounds_and_render() {
+    let mut grid: SparseGrid<char> = [(Point::new(-1, 0), '#'), (Point::new(1, 2), '#')]
+        .into_iter()
+        .collect();
+    let bounds = grid.bounds().unwrap();
+    assert_eq!(bounds, (Point::new(-1, 0), Point::new(1, 2)));
+    assert_eq!(
+        grid.render(bounds, |cell| *cell.unwrap_or(&'.')),
+        "#..\n...\n..#\n"
+    );
+    grid.remove(Point::new(1, 2));
+    assert_eq!(grid.bounds(), Some((Point::new(-1, 0), Point::new(-1, 0))));
+}
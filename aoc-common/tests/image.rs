@@ -0,0 +1,28 @@
+use aoc_common::{Colour, Frame, Image, Point, BACKGROUND};
+
+#[test]
+fn images_from_frames() {
+    let mut frame = Frame::new("sand");
+    frame.push_row([('o', Colour::Yellow), (' ', Colour::Plain)]);
+    frame.push_row([('#', Colour::Grey)]);
+    let image = Image::from_frame(&frame);
+    assert_eq!((image.width(), image.height()), (2, 2));
+    assert_eq!(image[Point::new(0, 0)], Colour::Yellow.rgb());
+    // Spaces and the end of a short row are left blank
+    assert_eq!(image[Point::new(1, 0)], BACKGROUND);
+    assert_eq!(image[Point::new(1, 1)], BACKGROUND);
+
+    let scaled = image.scaled(3);
+    assert_eq!((scaled.width(), scaled.height()), (6, 6));
+    assert_eq!(scaled[Point::new(2, 5)], Colour::Grey.rgb());
+    assert_eq!(scaled[Point::new(3, 2)], BACKGROUND);
+
+    let centred = image.centred(4, 3);
+    assert_eq!((centred.width(), centred.height()), (4, 3));
+    assert_eq!(centred[Point::new(1, 0)], Colour::Yellow.rgb());
+
+    let mut ppm = Vec::new();
+    image.write_ppm(&mut ppm).unwrap();
+    assert!(ppm.starts_with(b"P6\n2 2\n255\n"));
+    assert_eq!(ppm.len(), 11 + 2 * 2 * 3);
+}
@@ -0,0 +1,86 @@
+use aoc_common::{astar, bfs, bfs_reach, dijkstra, dijkstra_reach, Grid, Point};
+
+// A wall splits the map, so the way round is through the gap at the bottom
+const MAZE: &str = "\
+..#..
+..#..
+.....
+";
+
+fn maze() -> Grid<bool> {
+    Grid::parse(MAZE, "a wall # or open .", |ch| match ch {
+        '.' => Some(true),
+        '#' => Some(false),
+        _ => None,
+    })
+    .unwrap()
+}
+
+#[test]
+fn bfs_finds_the_shortest_path() {
+    let grid = maze();
+    let goal = Point::new(4, 0);
+    let successors = |&pt: &Point<usize>| grid.neighbours4(pt).filter(|&next| grid[next]);
+    let path = bfs([Point::new(0, 0)], successors, |&pt| pt == goal).unwrap();
+    assert_eq!(path.cost, 8);
+    assert_eq!(path.nodes.len(), 9);
+    assert_eq!((*path.start(), *path.goal()), (Point::new(0, 0), goal));
+    assert!(path.nodes.iter().all(|&pt| grid[pt]));
+
+    // Starting from both sides, the nearer start wins
+    let path = bfs([Point::new(0, 0), Point::new(3, 1)], successors, |&pt| {
+        pt == goal
+    })
+    .unwrap();
+    assert_eq!((path.cost, *path.start()), (2, Point::new(3, 1)));
+
+    let reached = bfs_reach([Point::new(0, 0)], successors);
+    assert_eq!(reached.len(), 13);
+    assert_eq!(reached.cost(&Point::new(2, 2)), Some(4));
+    assert!(!reached.contains(&Point::new(2, 0)));
+    assert_eq!(reached.path_to(&goal).unwrap().cost, 8);
+}
+
+#[test]
+fn weighted_searches_agree() {
+    // Going right costs 5, anything else costs 1
+    let successors = |&pt: &Point<i32>| {
+        pt.neighbours4()
+            .into_iter()
+            .filter(|next| (0..4).contains(&next.x) && (0..4).contains(&next.y))
+            .map(move |next| (next, if next.x > pt.x { 5 } else { 1 }))
+    };
+    let start = Point::new(0, 0);
+    let goal = Point::new(3, 3);
+
+    let path = dijkstra([start], successors, |&pt| pt == goal).unwrap();
+    assert_eq!(path.cost, 18);
+    let path = astar(
+        [start],
+        successors,
+        |pt| pt.manhattan(goal),
+        |&pt| pt == goal,
+    )
+    .unwrap();
+    assert_eq!(path.cost, 18);
+
+    let reached = dijkstra_reach([goal], successors);
+    assert_eq!(reached.cost(&start), Some(6));
+    assert_eq!(reached.len(), 16);
+}
+
+#[test]
+fn unreachable_goals() {
+    let grid = maze();
+    let successors = |&pt: &Point<usize>| grid.neighbours4(pt).filter(|&next| grid[next]);
+    assert_eq!(
+        bfs([Point::new(0, 0)], successors, |&pt| pt == Point::new(2, 0)),
+        None
+    );
+    let none = dijkstra(
+        [0_u32],
+        |&n| (n < 10).then_some((n + 2, 1_u32)),
+        |&n| n == 5,
+    );
+    assert_eq!(none, None);
+}
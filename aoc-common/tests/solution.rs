@@ -0,0 +1,21 @@
+use aoc_common::Answer;
+
+#[test]
+fn answers_from_numbers() {
+    assert_eq!(Answer::from(-3_i32), Answer::Num(-3));
+    assert_eq!(Answer::from(u32::MAX), Answer::Num(4_294_967_295));
+    assert_eq!(Answer::from(i64::MAX as u64), Answer::Num(i64::MAX));
+    assert_eq!(Answer::from(42_usize), Answer::Num(42));
+}
+
+// Too big for an i64, so kept as text instead of wrapping to a negative
+#[test]
+fn huge_answers_stay_whole() {
+    let answer = Answer::from(u64::MAX);
+    assert_eq!(answer, Answer::Text("18446744073709551615".to_string()));
+    assert_eq!(answer.to_string(), "18446744073709551615");
+    assert_eq!(
+        Answer::from(i64::MAX as usize + 1).to_string(),
+        "9223372036854775808"
+    );
+}
@@ -0,0 +1,112 @@
+use aoc_common::{Animate, Draw, Generate, ParseError, Rng, Solution};
+
+type Parser = fn(&str) -> Result<Box<dyn Solution>, ParseError>;
+
+fn parse<T: Solution + 'static>(input: &str) -> Result<Box<dyn Solution>, ParseError> {
+    Ok(Box::new(T::parse(input)?))
+}
+
+// Looks up how to parse a day's input into its puzzle
+pub fn parser(day: u8) -> Option<Parser> {
+    let parser: Parser = match day {
+        1 => parse::<day1::CalorieLedger>,
+        2 => parse::<day2::StrategyGuide>,
+        3 => parse::<day3::Puzzle>,
+        4 => parse::<day4::Puzzle>,
+        5 => parse::<day5::Puzzle>,
+        6 => parse::<day6::Puzzle>,
+        7 => parse::<day7::Puzzle>,
+        8 => parse::<day8::Forest>,
+        9 => parse::<day9::Puzzle>,
+        10 => parse::<day10::Cpu>,
+        11 => parse::<day11::Puzzle>,
+        12 => parse::<day12::HeightMap>,
+        13 => parse::<day13::Puzzle>,
+        14 => parse::<day14::Cave>,
+        15 => parse::<day15::SensorNetwork>,
+        16 => parse::<day16::Cave>,
+        17 => parse::<day17::Cave>,
+        18 => parse::<day18::Droplet>,
+        19 => parse::<day19::Factory>,
+        20 => parse::<day20::Puzzle>,
+        21 => parse::<day21::Puzzle>,
+        22 => parse::<day22::Puzzle>,
+        23 => parse::<day23::Puzzle>,
+        24 => parse::<day24::Puzzle>,
+        25 => parse::<day25::Puzzle>,
+        _ => return None,
+    };
+    Some(parser)
+}
+
+type Animator = fn(&str) -> Result<Box<dyn Animate>, ParseError>;
+
+fn animate<T: Solution + Animate + 'static>(input: &str) -> Result<Box<dyn Animate>, ParseError> {
+    Ok(Box::new(T::parse(input)?))
+}
+
+// Looks up how to parse the input of a day that can be watched
+pub fn animator(day: u8) -> Option<Animator> {
+    let animator: Animator = match day {
+        9 => animate::<day9::Puzzle>,
+        14 => animate::<day14::Cave>,
+        17 => animate::<day17::Cave>,
+        23 => animate::<day23::Puzzle>,
+        24 => animate::<day24::Puzzle>,
+        _ => return None,
+    };
+    Some(animator)
+}
+
+type Drawer = fn(&str) -> Result<Box<dyn Draw>, ParseError>;
+
+fn draw<T: Solution + Draw + 'static>(input: &str) -> Result<Box<dyn Draw>, ParseError> {
+    Ok(Box::new(T::parse(input)?))
+}
+
+// Looks up how to parse the input of a day that can be saved as a picture
+pub fn drawer(day: u8) -> Option<Drawer> {
+    let drawer: Drawer = match day {
+        8 => draw::<day8::Forest>,
+        12 => draw::<day12::HeightMap>,
+        14 => draw::<day14::Cave>,
+        23 => draw::<day23::Puzzle>,
+        _ => return None,
+    };
+    Some(drawer)
+}
+
+type Generator = fn(usize, &mut Rng) -> String;
+
+// Looks up how to make up an input for a day
+pub fn generator(day: u8) -> Option<Generator> {
+    let generator: Generator = match day {
+        1 => day1::CalorieLedger::generate,
+        2 => day2::StrategyGuide::generate,
+        3 => day3::Puzzle::generate,
+        4 => day4::Puzzle::generate,
+        5 => day5::Puzzle::generate,
+        6 => day6::Puzzle::generate,
+        7 => day7::Puzzle::generate,
+        8 => day8::Forest::generate,
+        9 => day9::Puzzle::generate,
+        10 => day10::Cpu::generate,
+        11 => day11::Puzzle::generate,
+        12 => day12::HeightMap::generate,
+        13 => day13::Puzzle::generate,
+        14 => day14::Cave::generate,
+        15 => day15::SensorNetwork::generate,
+        16 => day16::Cave::generate,
+        17 => day17::Cave::generate,
+        18 => day18::Droplet::generate,
+        19 => day19::Factory::generate,
+        20 => day20::Puzzle::generate,
+        21 => day21::Puzzle::generate,
+        22 => day22::Puzzle::generate,
+        23 => day23::Puzzle::generate,
+        24 => day24::Puzzle::generate,
+        25 => day25::Puzzle::generate,
+        _ => return None,
+    };
+    Some(generator)
+}
@@ -0,0 +1,54 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    path::Path,
+};
+
+use aoc_common::{Animate, Draw, Gif, Image};
+
+// How to turn a simulation's frames into a GIF
+pub struct GifOptions {
+    pub scale: usize,
+    pub fps: u32,
+    pub every: usize,
+    pub limit: usize,
+}
+
+// Saves a picture of the puzzle, as a PPM if the path ends in .ppm and a
+// PNG otherwise
+pub fn picture(puzzle: &dyn Draw, part: u8, path: &Path, scale: usize) -> io::Result<()> {
+    let image = puzzle.image(part).scaled(scale);
+    let out = BufWriter::new(File::create(path)?);
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("ppm") => image.write_ppm(out),
+        _ => image.write_png(out),
+    }
+}
+
+// Saves a simulation as an animated GIF. The frames are made twice, once to
+// find the biggest so the rest can be centred in it and again to write
+// them, which saves holding every frame in memory.
+pub fn animation(
+    puzzle: &dyn Animate,
+    part: u8,
+    path: &Path,
+    options: &GifOptions,
+) -> io::Result<()> {
+    let frames = || {
+        puzzle
+            .frames(part)
+            .step_by(options.every.max(1))
+            .take(options.limit)
+    };
+    let (width, height) = frames().fold((0, 0), |(width, height), frame| {
+        (width.max(frame.width()), height.max(frame.height()))
+    });
+    let scale = options.scale.max(1);
+    let delay = (100 / options.fps.max(1)).max(2) as u16;
+    let out = BufWriter::new(File::create(path)?);
+    let mut gif = Gif::new(out, width * scale, height * scale, delay)?;
+    for frame in frames() {
+        gif.add(&Image::from_frame(&frame).scaled(scale))?;
+    }
+    gif.finish()?.flush()
+}
@@ -0,0 +1,293 @@
+use std::{
+    path::{Path, PathBuf},
+    process,
+};
+
+use aoc_common::{
+    init_logging, play, print_results, record_answers, solve, verify, Format, Input, ParseError,
+    Rng, Solved, Status,
+};
+use clap::{Parser, Subcommand};
+use rayon::prelude::*;
+use tracing::info_span;
+
+mod days;
+mod export;
+mod memory;
+mod timings;
+
+#[global_allocator]
+static ALLOCATOR: memory::Counting = memory::Counting;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 runner")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day, or every day with an input.txt when --day is
+    /// omitted
+    Run {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+        /// Part to solve, both parts are solved when omitted
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Puzzle input, defaults to dayN/input.txt; "-" reads stdin
+        #[arg(long, requires = "day")]
+        input: Option<PathBuf>,
+        /// Threads to solve on, shared by the days and the days that split
+        /// their own work; defaults to one per CPU
+        #[arg(long)]
+        jobs: Option<usize>,
+        /// Save answers not yet in answers.toml as the correct ones
+        #[arg(long)]
+        record: bool,
+        /// How to print the answers: text, plain or json
+        #[arg(long, default_value = "text")]
+        format: Format,
+        /// Log the day's diagnostics to stderr, -vv for every step. RUST_LOG
+        /// also selects what is logged
+        #[arg(short, long, action = clap::ArgAction::Count)]
+        verbose: u8,
+    },
+    /// Time every day's input.txt, reporting parse and part times and peak
+    /// memory
+    Timings,
+    /// Watch a day's simulation play out in the terminal (days 9, 14, 17,
+    /// 23 and 24)
+    Watch {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Puzzle input, defaults to dayN/input.txt; "-" reads stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Frames a second to start at, + and - change it while playing
+        #[arg(long, default_value_t = 30)]
+        fps: u32,
+    },
+    /// Save a picture of a day's puzzle as a PNG or PPM (days 8, 12, 14 and
+    /// 23), or its simulation as an animated GIF (days 9, 14, 17, 23 and 24)
+    Export {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Puzzle input, defaults to dayN/input.txt; "-" reads stdin
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// File to save to, its extension picks the format: png, ppm or gif
+        #[arg(long)]
+        output: PathBuf,
+        /// Pixels along each side of a cell
+        #[arg(long, default_value_t = 4)]
+        scale: usize,
+        /// Frames a second in a GIF
+        #[arg(long, default_value_t = 10)]
+        fps: u32,
+        /// Keep only every Nth frame of a GIF
+        #[arg(long, default_value_t = 1)]
+        every: usize,
+        /// Stop a GIF after this many frames, so day 17 part 2's endless
+        /// rocks still make one
+        #[arg(long, default_value_t = 10_000)]
+        frames: usize,
+    },
+    /// Make up a valid input for a day to stress it at sizes real inputs
+    /// never reach, printed so it can be piped into `run --input -`
+    Generate {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// How big to make it, in the day's own units (see the README)
+        #[arg(long)]
+        size: usize,
+        /// The same seed and size always make the same input
+        #[arg(long, default_value_t = 0)]
+        seed: u64,
+    },
+}
+
+// Reads a day's input, exiting if it can't be read
+fn read_or_exit(day: u8, input: Option<PathBuf>) -> (String, String) {
+    let input = input.unwrap_or_else(|| format!("day{}/input.txt", day).into());
+    let source = input.display().to_string();
+    match Input::from(input).read() {
+        Ok(contents) => (source, contents),
+        Err(err) => {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+    }
+}
+
+// Reads, parses and solves a day's input, or the chosen part of it
+fn solve_day(
+    day: u8,
+    input: Option<PathBuf>,
+    part: Option<u8>,
+) -> Result<(Input, Vec<Solved>), String> {
+    let input = input.unwrap_or_else(|| format!("day{}/input.txt", day).into());
+    let source = input.display().to_string();
+    let input = Input::from(input);
+    let contents = input.clone().read().map_err(|err| err.to_string())?;
+    let _span = info_span!("day", day).entered();
+    let puzzle =
+        days::parser(day).unwrap()(&contents).map_err(|err| format!("{}: {}", source, err))?;
+    let results = [1, 2]
+        .into_iter()
+        .filter(|&p| part.is_none_or(|part| part == p))
+        .map(|p| solve(puzzle.as_ref(), p))
+        .collect();
+    Ok((input, results))
+}
+
+// Parses a day's input with `parse`, exiting if it doesn't parse
+fn parse_input_or_exit<T>(
+    source: &str,
+    contents: &str,
+    parse: fn(&str) -> Result<T, ParseError>,
+) -> T {
+    match parse(contents) {
+        Ok(puzzle) => puzzle,
+        Err(err) => {
+            eprintln!("{}: {}", source, err);
+            process::exit(1);
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            jobs,
+            record,
+            format,
+            verbose,
+        } => {
+            init_logging(verbose);
+            if let Err(err) = rayon::ThreadPoolBuilder::new()
+                .num_threads(jobs.unwrap_or(0))
+                .build_global()
+            {
+                eprintln!("Could not start the threads to solve on: {}", err);
+                process::exit(1);
+            }
+            let days: Vec<u8> = match day {
+                Some(day) => vec![day],
+                None => (1..=25)
+                    .filter(|day| Path::new(&format!("day{}/input.txt", day)).exists())
+                    .collect(),
+            };
+            // Every day is solved before any is reported, so the output comes
+            // in order whichever finishes first
+            let solved: Vec<_> = days
+                .par_iter()
+                .map(|&day| solve_day(day, input.clone(), part))
+                .collect();
+
+            let mut ok = true;
+            for (&day, solved) in days.iter().zip(solved) {
+                let (input, results) = match solved {
+                    Ok(solved) => solved,
+                    Err(err) => {
+                        eprintln!("{}", err);
+                        ok = false;
+                        continue;
+                    }
+                };
+                let statuses = verify(day, &input, &results);
+                print_results(day, format, &results, &statuses);
+                if record {
+                    record_answers(day, &input, &results);
+                }
+                ok &= !statuses
+                    .iter()
+                    .any(|status| matches!(status, Status::Fail { .. }));
+            }
+            if !ok {
+                process::exit(1);
+            }
+        }
+        Command::Timings => {
+            if !timings::report() {
+                process::exit(1);
+            }
+        }
+        Command::Watch {
+            day,
+            part,
+            input,
+            fps,
+        } => {
+            let Some(animator) = days::animator(day) else {
+                eprintln!("Day {} has no simulation to watch", day);
+                process::exit(2);
+            };
+            let (source, contents) = read_or_exit(day, input);
+            let puzzle = parse_input_or_exit(&source, &contents, animator);
+            if let Err(err) = play(puzzle.frames(part), fps) {
+                eprintln!("Could not play day {}: {}", day, err);
+                process::exit(1);
+            }
+        }
+        Command::Export {
+            day,
+            part,
+            input,
+            output,
+            scale,
+            fps,
+            every,
+            frames,
+        } => {
+            let saved = match output.extension().and_then(|ext| ext.to_str()) {
+                Some("gif") => {
+                    let Some(animator) = days::animator(day) else {
+                        eprintln!("Day {} has no simulation to save as a GIF", day);
+                        process::exit(2);
+                    };
+                    let (source, contents) = read_or_exit(day, input);
+                    let puzzle = parse_input_or_exit(&source, &contents, animator);
+                    let options = export::GifOptions {
+                        scale,
+                        fps,
+                        every,
+                        limit: frames,
+                    };
+                    export::animation(puzzle.as_ref(), part, &output, &options)
+                }
+                Some("png" | "ppm") => {
+                    let Some(drawer) = days::drawer(day) else {
+                        eprintln!("Day {} has no picture to save", day);
+                        process::exit(2);
+                    };
+                    let (source, contents) = read_or_exit(day, input);
+                    let puzzle = parse_input_or_exit(&source, &contents, drawer);
+                    export::picture(puzzle.as_ref(), part, &output, scale)
+                }
+                _ => {
+                    eprintln!("{}: save as a .png, .ppm or .gif", output.display());
+                    process::exit(2);
+                }
+            };
+            if let Err(err) = saved {
+                eprintln!("Could not save {}: {}", output.display(), err);
+                process::exit(1);
+            }
+        }
+        Command::Generate { day, size, seed } => {
+            let generate = days::generator(day).unwrap();
+            print!("{}", generate(size, &mut Rng::new(seed)));
+        }
+    }
+}
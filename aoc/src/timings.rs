@@ -0,0 +1,119 @@
+use std::{
+    fmt, fs, io,
+    time::{Duration, Instant},
+};
+
+use crate::{days, memory};
+
+struct Timing {
+    day: u8,
+    parse: Duration,
+    part1: Duration,
+    part2: Duration,
+    // Bytes allocated at once beyond what was in use before parsing
+    peak: usize,
+}
+
+fn time<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+// Times a day's input, or None when there is no input to time
+fn time_day(day: u8) -> Result<Option<Timing>, String> {
+    let path = format!("day{}/input.txt", day);
+    let input = match fs::read_to_string(&path) {
+        Ok(input) => input,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(format!("could not read {}: {}", path, err)),
+    };
+
+    let baseline = memory::reset_peak();
+    let (puzzle, parse) = time(|| days::parser(day).unwrap()(&input));
+    let puzzle = puzzle.map_err(|err| format!("{}: {}", path, err))?;
+    let (_, part1) = time(|| puzzle.part1());
+    let (_, part2) = time(|| puzzle.part2());
+
+    Ok(Some(Timing {
+        day,
+        parse,
+        part1,
+        part2,
+        peak: memory::peak_since(baseline),
+    }))
+}
+
+struct Elapsed(Duration);
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let secs = self.0.as_secs_f64();
+        let text = if secs >= 1.0 {
+            format!("{:.2} s", secs)
+        } else if secs >= 1e-3 {
+            format!("{:.2} ms", secs * 1e3)
+        } else {
+            format!("{:.2} µs", secs * 1e6)
+        };
+        f.pad(&text)
+    }
+}
+
+struct Bytes(usize);
+
+impl fmt::Display for Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = self.0 as f64;
+        let text = if bytes >= 1024.0 * 1024.0 {
+            format!("{:.1} MiB", bytes / (1024.0 * 1024.0))
+        } else if bytes >= 1024.0 {
+            format!("{:.1} KiB", bytes / 1024.0)
+        } else {
+            format!("{} B", self.0)
+        };
+        f.pad(&text)
+    }
+}
+
+/// Solves every day's `dayN/input.txt` and prints how long parsing and each
+/// part took and the most memory it needed. Days without an input are
+/// skipped; returns false if any day's input could not be read or parsed.
+pub fn report() -> bool {
+    let mut timings = Vec::new();
+    let mut ok = true;
+    for day in 1..=25 {
+        match time_day(day) {
+            Ok(Some(timing)) => timings.push(timing),
+            Ok(None) => eprintln!("skipping day {}: no input", day),
+            Err(err) => {
+                eprintln!("day {}: {}", day, err);
+                ok = false;
+            }
+        }
+    }
+
+    println!(
+        "{:>5} {:>12} {:>12} {:>12} {:>12}",
+        "Day", "Parse", "Part 1", "Part 2", "Peak memory"
+    );
+    for timing in &timings {
+        println!(
+            "{:>5} {:>12} {:>12} {:>12} {:>12}",
+            timing.day,
+            Elapsed(timing.parse),
+            Elapsed(timing.part1),
+            Elapsed(timing.part2),
+            Bytes(timing.peak)
+        );
+    }
+    println!(
+        "{:>5} {:>12} {:>12} {:>12} {:>12}",
+        "Total",
+        Elapsed(timings.iter().map(|timing| timing.parse).sum()),
+        Elapsed(timings.iter().map(|timing| timing.part1).sum()),
+        Elapsed(timings.iter().map(|timing| timing.part2).sum()),
+        Bytes(timings.iter().map(|timing| timing.peak).max().unwrap_or(0))
+    );
+    ok
+}
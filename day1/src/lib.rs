@@ -1,23 +1,298 @@
-#[derive(Debug)]
+use aoc_common::{blocks, number, Answer, Generate, ParseError, Rng, Solution};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+    fmt,
+    io::{self, BufRead},
+};
+
+/// What an elf carries, kept as running figures rather than every item.
+/// Totals are 64 bit and checked, so no inventory can silently wrap.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Elf {
-    items: Vec<i32>,
-    total: i32,
+    items: usize,
+    total: i64,
+    max_item: i64,
 }
 
 impl Elf {
     pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The elf with one more item, or None if its total would no longer fit.
+    pub fn checked_add(self, item: i64) -> Option<Self> {
+        Some(Self {
+            items: self.items + 1,
+            total: self.total.checked_add(item)?,
+            max_item: if self.items == 0 {
+                item
+            } else {
+                self.max_item.max(item)
+            },
+        })
+    }
+
+    pub fn get_total(&self) -> i64 {
+        self.total
+    }
+
+    pub fn items(&self) -> usize {
+        self.items
+    }
+
+    /// The biggest item, 0 for an elf carrying nothing.
+    pub fn max_item(&self) -> i64 {
+        self.max_item
+    }
+
+    /// Calories per item, 0 for an elf carrying nothing.
+    pub fn mean(&self) -> f64 {
+        match self.items {
+            0 => 0.0,
+            items => self.total as f64 / items as f64,
+        }
+    }
+}
+
+// An elf in the running for the top places, ordered so the heap's top is
+// the one to drop first: the smallest total, listed last among equals
+#[derive(Debug, PartialEq, Eq)]
+struct Placed {
+    index: usize,
+    elf: Elf,
+}
+
+impl Ord for Placed {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.elf.total, Reverse(other.index)).cmp(&(self.elf.total, Reverse(self.index)))
+    }
+}
+
+impl PartialOrd for Placed {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// The best `k` elves seen so far, never holding more than `k`
+struct Podium {
+    k: usize,
+    best: BinaryHeap<Placed>,
+}
+
+impl Podium {
+    fn new(k: usize) -> Self {
         Self {
-            items: Vec::new(),
-            total: 0,
+            k,
+            best: BinaryHeap::with_capacity(k + 1),
+        }
+    }
+    fn push(&mut self, index: usize, elf: Elf) {
+        if self.k == 0 {
+            return;
+        }
+        self.best.push(Placed { index, elf });
+        if self.best.len() > self.k {
+            self.best.pop();
         }
     }
+    // Most first
+    fn into_vec(self) -> Vec<(usize, Elf)> {
+        self.best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|placed| (placed.index, placed.elf))
+            .collect()
+    }
+}
+
+/// Calories carried by some elves, or None if they're too many to add up.
+pub fn total(elves: &[(usize, Elf)]) -> Option<i64> {
+    elves
+        .iter()
+        .try_fold(0i64, |sum, (_, elf)| sum.checked_add(elf.total))
+}
+
+/// Why an inventory being read a line at a time couldn't be ranked.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
 
-    pub fn add(&mut self, item: i32) {
-        self.items.push(item);
-        self.total += item;
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReadError::Io(err) => write!(f, "could not read the inventory: {}", err),
+            ReadError::Parse(err) => write!(f, "{}", err),
+        }
     }
+}
 
-    pub fn get_total(&self) -> i32 {
-        self.total
+impl std::error::Error for ReadError {}
+
+impl From<io::Error> for ReadError {
+    fn from(err: io::Error) -> Self {
+        ReadError::Io(err)
+    }
+}
+
+/// The `k` elves carrying the most, most first, from an inventory read a
+/// line at a time. Only the line being read and the best `k` elves are held,
+/// so an inventory of any size fits in the same memory.
+pub fn top_from_reader(mut reader: impl BufRead, k: usize) -> Result<Vec<(usize, Elf)>, ReadError> {
+    let mut podium = Podium::new(k);
+    let mut line = String::new();
+    let mut line_num = 0;
+    let mut index = 0;
+    let mut elf: Option<Elf> = None;
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line)?;
+        line_num += 1;
+        let token = line.trim();
+        // A blank line, or the end, finishes the elf being counted
+        if token.is_empty() {
+            if let Some(elf) = elf.take() {
+                podium.push(index, elf);
+                index += 1;
+            }
+            if read == 0 {
+                return Ok(podium.into_vec());
+            }
+            continue;
+        }
+        let at_line = |mut err: ParseError| {
+            err.line = line_num;
+            ReadError::Parse(err)
+        };
+        let item = number(&line, token).map_err(at_line)?;
+        elf = Some(elf.unwrap_or_default().checked_add(item).ok_or_else(|| {
+            at_line(ParseError::at(
+                &line,
+                token,
+                "calories adding up to fit in 64 bits",
+            ))
+        })?);
+    }
+}
+
+/// Every elf's inventory in the order they were listed, for ranking them by
+/// what they carry.
+#[derive(Debug, Default, Clone)]
+pub struct CalorieLedger {
+    elves: Vec<Elf>,
+}
+
+impl CalorieLedger {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an elf, returning its index.
+    pub fn push(&mut self, elf: Elf) -> usize {
+        self.elves.push(elf);
+        self.elves.len() - 1
+    }
+
+    /// Adds an elf carrying `items`, totting them up as they come so they're
+    /// never all held at once. Returns the elf's index, or None if its total
+    /// doesn't fit.
+    pub fn add_elf(&mut self, items: impl IntoIterator<Item = i64>) -> Option<usize> {
+        let elf = items
+            .into_iter()
+            .try_fold(Elf::new(), |elf, item| elf.checked_add(item))?;
+        Some(self.push(elf))
+    }
+
+    pub fn len(&self) -> usize {
+        self.elves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elves.is_empty()
+    }
+
+    pub fn elf(&self, index: usize) -> Option<&Elf> {
+        self.elves.get(index)
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        &self.elves
+    }
+
+    /// The `k` elves carrying the most with their indices, most first. Ties
+    /// go to the elf listed first. Only `k` elves are held while looking.
+    pub fn top_n(&self, k: usize) -> Vec<(usize, Elf)> {
+        let mut podium = Podium::new(k);
+        for (index, &elf) in self.elves.iter().enumerate() {
+            podium.push(index, elf);
+        }
+        podium.into_vec()
+    }
+
+    /// Calories carried by the `k` elves carrying the most, or None if
+    /// there are no elves or they carry too many to add up.
+    pub fn top_total(&self, k: usize) -> Option<i64> {
+        let top = self.top_n(k);
+        if top.is_empty() {
+            return None;
+        }
+        total(&top)
+    }
+
+    /// Where the elf at `index` places, 1 for carrying the most. Ties go to
+    /// the elf listed first, as with `top_n`.
+    pub fn rank(&self, index: usize) -> Option<usize> {
+        let elf = self.elves.get(index)?;
+        let ahead = self
+            .elves
+            .iter()
+            .enumerate()
+            .filter(|&(other, them)| {
+                them.total > elf.total || (them.total == elf.total && other < index)
+            })
+            .count();
+        Some(ahead + 1)
+    }
+}
+
+impl Solution for CalorieLedger {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut ledger = CalorieLedger::new();
+        // Each elf's inventory is a block of lines
+        for block in blocks(input) {
+            let mut elf = Elf::new();
+            for line in block.lines() {
+                let item = number(input, line.trim())?;
+                elf = elf.checked_add(item).ok_or_else(|| {
+                    ParseError::at(input, line, "calories adding up to fit in 64 bits")
+                })?;
+            }
+            ledger.push(elf);
+        }
+        Ok(ledger)
+    }
+    fn part1(&self) -> Answer {
+        self.top_total(1).map_or(Answer::None, Answer::from)
+    }
+    fn part2(&self) -> Answer {
+        self.top_total(3).map_or(Answer::None, Answer::from)
+    }
+}
+
+impl Generate for CalorieLedger {
+    // `size` elves, each with a handful of snacks
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let elves: Vec<String> = (0..size.max(1))
+            .map(|_| {
+                let items: Vec<String> = (0..rng.range(1..15))
+                    .map(|_| rng.range(1_000..60_000).to_string())
+                    .collect();
+                items.join("\n")
+            })
+            .collect();
+        elves.join("\n\n") + "\n"
     }
 }
@@ -2,40 +2,66 @@ use std::{
     env,
     fs::File,
     io::{self, BufRead, BufReader},
-    path::Path,
+    process,
 };
 
-use day1::Elf;
+use day1::{top_from_reader, CalorieLedger};
 
-fn lines_from_file(filename: impl AsRef<Path>) -> io::Result<Vec<String>> {
-    BufReader::new(File::open(filename)?).lines().collect()
+const USAGE: &str = "usage: day1 --stream [--top <k>] <input file | ->";
+// Listed with the options for solving when those are given wrong
+const MODES: &[&str] = &["--stream [--top <k>] <input file | ->"];
+
+// Ranks an inventory a line at a time rather than reading it whole, for ones
+// too big to hold: `day1 --stream [--top k] <input file | ->`
+fn stream(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut top = 3;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => {
+                let k = args.next().ok_or(USAGE)?;
+                top = k.parse().map_err(|_| USAGE)?;
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let path = path.ok_or(USAGE)?;
+    let reader: Box<dyn BufRead> = match path.as_str() {
+        "-" => Box::new(io::stdin().lock()),
+        _ => Box::new(BufReader::new(
+            File::open(&path).map_err(|err| format!("could not read {}: {}", path, err))?,
+        )),
+    };
+    let source = if path == "-" { "stdin" } else { &path };
+    let elves = top_from_reader(reader, top).map_err(|err| format!("{}: {}", source, err))?;
+    for (rank, (index, elf)) in elves.iter().enumerate() {
+        println!(
+            "{}. elf {}: {} calories in {} items, the biggest {}, {:.1} on average",
+            rank + 1,
+            index + 1,
+            elf.get_total(),
+            elf.items(),
+            elf.max_item(),
+            elf.mean()
+        );
+    }
+    match day1::total(&elves) {
+        Some(total) => println!("Total: {}", total),
+        None => println!("Total: too many calories to add up in 64 bits"),
+    }
+    Ok(())
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let filename = &args[1];
-    let lines = lines_from_file(filename).expect("Could not load");
-
-    let mut elves: Vec<Elf> = Vec::new();
-
-    let mut new_elf = Elf::new();
-    for line in lines {
-        if line.is_empty() {
-            elves.push(new_elf);
-            new_elf = Elf::new();
-        } else {
-            new_elf.add(line.parse::<i32>().unwrap());
+    let mut args = env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("--stream") {
+        args.next();
+        if let Err(err) = stream(args) {
+            eprintln!("{}", err);
+            process::exit(1);
         }
+        return;
     }
-
-    elves.push(new_elf);
-
-    elves.sort_by(|a, b| b.get_total().cmp(&a.get_total()));
-
-    println!(
-        "{:?} {}",
-        elves,
-        elves[0].get_total() + elves[1].get_total() + elves[2].get_total()
-    );
+    aoc_common::run_with_modes::<CalorieLedger>(1, MODES);
 }
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day1::CalorieLedger;
+
+#[test]
+fn sample() {
+    check::<CalorieLedger>("sample.txt", 24_000, 45_000);
+}
+
+#[test]
+fn input() {
+    check_input::<CalorieLedger>("input.txt", 71_471, 211_189);
+}
+
+#[test]
+fn generated() {
+    check_generated::<CalorieLedger>(100, 3);
+}
@@ -0,0 +1,91 @@
+use aoc_common::{Answer, Solution};
+use day1::{top_from_reader, total, CalorieLedger, Elf, ReadError};
+
+fn ledger() -> CalorieLedger {
+    let mut ledger = CalorieLedger::new();
+    for items in [
+        vec![1000, 2000, 3000],
+        vec![4000],
+        vec![5000, 6000],
+        vec![7000, 8000, 9000],
+        vec![10000],
+    ] {
+        ledger.add_elf(items).unwrap();
+    }
+    ledger
+}
+
+#[test]
+fn top_elves() {
+    let ledger = ledger();
+    let top: Vec<usize> = ledger.top_n(3).iter().map(|&(index, _)| index).collect();
+    assert_eq!(top, [3, 2, 4]);
+    assert_eq!(ledger.top_total(3), Some(45_000));
+    assert_eq!(ledger.top_n(10).len(), 5);
+    assert!(ledger.top_n(0).is_empty());
+    assert_eq!(ledger.top_total(0), None);
+
+    let empty = CalorieLedger::parse("").unwrap();
+    assert_eq!(empty.top_total(3), None);
+    assert_eq!(empty.part1(), Answer::None);
+
+    assert_eq!(ledger.rank(3), Some(1));
+    assert_eq!(ledger.rank(1), Some(5));
+    assert_eq!(ledger.rank(5), None);
+}
+
+#[test]
+fn ties_go_to_the_first_elf() {
+    let mut ledger = CalorieLedger::new();
+    for total in [5, 9, 5, 9] {
+        ledger.add_elf([total]);
+    }
+    let top: Vec<usize> = ledger.top_n(3).iter().map(|&(index, _)| index).collect();
+    assert_eq!(top, [1, 3, 0]);
+    assert_eq!(ledger.rank(3), Some(2));
+    assert_eq!(ledger.rank(2), Some(4));
+}
+
+#[test]
+fn elf_stats() {
+    let ledger = ledger();
+    let elf = ledger.elf(0).unwrap();
+    assert_eq!(
+        (elf.items(), elf.get_total(), elf.max_item()),
+        (3, 6000, 3000)
+    );
+    assert_eq!(elf.mean(), 2000.0);
+    assert_eq!(Elf::new().mean(), 0.0);
+
+    let mut ledger = CalorieLedger::new();
+    assert_eq!(ledger.add_elf([i64::MAX, 1]), None);
+    assert!(ledger.is_empty());
+}
+
+#[test]
+fn streamed_like_parsed() {
+    let input = std::fs::read_to_string("sample.txt").unwrap();
+    let ledger = CalorieLedger::parse(&input).unwrap();
+    let top = top_from_reader(input.as_bytes(), 3).unwrap();
+    assert_eq!(top, ledger.top_n(3));
+    assert_eq!(total(&top), Some(45_000));
+    assert!(top_from_reader("".as_bytes(), 3).unwrap().is_empty());
+}
+
+#[test]
+fn stream_errors() {
+    let line_of = |input: &str| match top_from_reader(input.as_bytes(), 1) {
+        Err(ReadError::Parse(err)) => err.line,
+        other => panic!("expected a parse error, got {:?}", other),
+    };
+    assert_eq!(line_of("1\n2\n\nthree\n"), 4);
+    assert_eq!(
+        line_of("9223372036854775807\n\n1\n9223372036854775807\n"),
+        4
+    );
+
+    // Totals that fit but whose sum doesn't are left to the caller
+    let input = "9223372036854775807\n\n9223372036854775807\n";
+    let top = top_from_reader(input.as_bytes(), 2).unwrap();
+    assert_eq!(total(&top), None);
+}
@@ -0,0 +1,10 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day10::Cpu;
+
+fn solve(c: &mut Criterion) {
+    bench::<Cpu>(c, "day10", &["sample2.txt", "input.txt"]);
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
@@ -1,9 +1,10 @@
+use aoc_common::{number, parse_lines, Answer, Generate, ParseError, Rng, Solution};
 use std::collections::HashMap;
 
 #[derive(Debug)]
 enum Instruction {
-    NOP,
-    ADDX(i32),
+    Nop,
+    Addx(i32),
 }
 
 #[derive(Debug)]
@@ -13,24 +14,31 @@ pub struct Cpu {
     highest_cycle: u32,
 }
 
+impl Default for Cpu {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Cpu {
     pub fn new() -> Self {
         let mut x_at = HashMap::new();
         x_at.insert(0, 1);
         Self {
             program: Vec::new(),
-            x_at: x_at,
+            x_at,
             highest_cycle: 0,
         }
     }
-    pub fn add_line(&mut self, line: String) {
-        let v: Vec<String> = line.split_whitespace().map(str::to_string).collect();
-        if v[0] == "noop" {
-            self.program.push(Instruction::NOP);
-        } else {
-            let op1 = v[1].parse::<i32>().unwrap();
-            self.program.push(Instruction::ADDX(op1));
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let v: Vec<&str> = line.split_whitespace().collect();
+        match v[..] {
+            ["noop"] => self.program.push(Instruction::Nop),
+            ["addx", op1] => self.program.push(Instruction::Addx(number(line, op1)?)),
+            ["addx"] => return Err(ParseError::at_end(line, "a value to add")),
+            _ => return Err(ParseError::at(line, line.trim_start(), "noop or addx <n>")),
         }
+        Ok(())
     }
     pub fn execute(&mut self) {
         let mut cycle: u32 = 0;
@@ -38,8 +46,8 @@ impl Cpu {
 
         for inst in &self.program {
             match inst {
-                Instruction::NOP => cycle += 1,
-                Instruction::ADDX(op1) => {
+                Instruction::Nop => cycle += 1,
+                Instruction::Addx(op1) => {
                     cycle += 2;
                     x_reg += op1;
                     self.x_at.insert(cycle, x_reg);
@@ -59,12 +67,72 @@ impl Cpu {
         while check_cycle > 1 {
             match self.x_at.get(&check_cycle) {
                 Option::None => check_cycle -= 1,
-                Option::Some(val) => {
-                    // println!("Returning {} at {}", val, check_cycle);
-                    return *val;
-                }
+                Option::Some(val) => return *val,
+            }
+        }
+        1
+    }
+}
+
+impl Solution for Cpu {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut cpu = Cpu::new();
+        let mut x_reg: i32 = 1;
+        parse_lines(input, |line| {
+            cpu.add_line(line)?;
+            // Check X stays in range here, where the line to blame is known
+            if let Some(Instruction::Addx(op1)) = cpu.program.last() {
+                x_reg = x_reg.checked_add(*op1).ok_or_else(|| {
+                    ParseError::at(line, line, "a value keeping X within 32 bits")
+                })?;
+            }
+            Ok(())
+        })?;
+        cpu.execute();
+        Ok(cpu)
+    }
+    fn part1(&self) -> Answer {
+        (20..=220)
+            .step_by(40)
+            .map(|cycle| cycle * self.get_x_at(cycle))
+            .sum::<i32>()
+            .into()
+    }
+    // Draws the CRT, one line of text per row of pixels
+    fn part2(&self) -> Answer {
+        let mut screen = String::new();
+
+        for cycle in 0..240 {
+            let remainder = cycle % 40;
+            if remainder == 0 && cycle != 0 {
+                screen.push('\n');
             }
+            let x = self.get_x_at(cycle + 1);
+            let ch = if (x == remainder - 1) || (x == remainder) || (x == remainder + 1) {
+                '#'
+            } else {
+                '.'
+            };
+            screen.push(ch);
         }
-        return 1;
+        screen.into()
+    }
+}
+
+impl Generate for Cpu {
+    // `size` instructions, at least enough to draw the whole screen, with
+    // X kept near the screen
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut x_reg = 1;
+        (0..size.max(240))
+            .map(|_| match rng.chance(40) {
+                true => "noop\n".to_string(),
+                false => {
+                    let value = rng.range(-5..6).clamp(-x_reg, 40 - x_reg);
+                    x_reg += value;
+                    format!("addx {}\n", value)
+                }
+            })
+            .collect()
     }
 }
@@ -0,0 +1,33 @@
+use aoc_common::{check, check_generated, check_input};
+use day10::Cpu;
+
+// sample.txt is the three line program from the puzzle text, too short to
+// draw anything, so the larger example is the one with known answers
+#[test]
+fn sample2() {
+    let screen = "\
+##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######.....";
+    check::<Cpu>("sample2.txt", 13_140, screen);
+}
+
+#[test]
+fn input() {
+    let screen = "\
+####..##..####.#..#.####..##..#....###..
+#....#..#....#.#..#....#.#..#.#....#..#.
+###..#......#..#..#...#..#..#.#....#..#.
+#....#.....#...#..#..#...####.#....###..
+#....#..#.#....#..#.#....#..#.#....#.#..
+####..##..####..##..####.#..#.####.#..#.";
+    check_input::<Cpu>("input.txt", 16_020, screen);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Cpu>(300, 3);
+}
@@ -1,11 +1,13 @@
-#[derive(Debug)]
+use aoc_common::{blocks, number, Answer, Generate, ParseError, Rng, Solution};
+
+#[derive(Debug, Clone)]
 enum Operation {
-    TIMES(i64),
-    ADD(i64),
-    SQUARE,
+    Times(i64),
+    Add(i64),
+    Square,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Monkey {
     pub items: Vec<i64>,
     op: Operation,
@@ -15,76 +17,109 @@ pub struct Monkey {
     pub num_inspected: u32,
 }
 
-impl Monkey {
-    fn parse_items(&mut self, line: &String) {
-        let tmp_string: String = line.replace(",", " ");
-        let tokens: Vec<&str> = tmp_string.split_whitespace().collect();
-
-        for index in 2..tokens.len() {
-            self.items.push(tokens[index].parse().unwrap());
-        }
-    }
-    fn parse_operation(&mut self, line: &String) {
-        let tokens: Vec<&str> = line.split_whitespace().collect();
-        match tokens[4] {
-            "*" => {
-                if tokens[5] == "old" {
-                    self.op = Operation::SQUARE;
-                } else {
-                    self.op = Operation::TIMES(tokens[5].parse().unwrap());
-                }
+// The rest of a line after its label, e.g. the 23 of "Test: divisible by 23"
+fn field<'a>(block: &str, line: Option<&'a str>, label: &str) -> Result<&'a str, ParseError> {
+    let expected = format!("\"{}\"", label);
+    match line {
+        Some(line) => {
+            let line = line.trim_start();
+            match line.strip_prefix(label) {
+                Some(rest) => Ok(rest.trim()),
+                None => Err(ParseError::at(block, line, expected)),
             }
-            "+" => self.op = Operation::ADD(tokens[5].parse().unwrap()),
-            _ => panic!("Unknown token!"),
         }
+        None => Err(ParseError::at_end(block, expected)),
     }
-    fn parse_test(&mut self, line: &String) {
-        let tokens: Vec<&str> = line.split_whitespace().collect();
-        self.test = tokens[tokens.len() - 1].parse().unwrap();
+}
+
+impl Monkey {
+    fn parse_items(&mut self, block: &str, items: &str) -> Result<(), ParseError> {
+        for token in items.split(',') {
+            self.items.push(number(block, token.trim())?);
+        }
+        Ok(())
     }
-    fn parse_pass_true(&mut self, line: &String) {
-        let tokens: Vec<&str> = line.split_whitespace().collect();
-        self.pass_true = tokens[tokens.len() - 1].parse().unwrap();
+    fn parse_operation(&mut self, block: &str, operation: &str) -> Result<(), ParseError> {
+        let tokens: Vec<&str> = operation.split_whitespace().collect();
+        self.op = match tokens[..] {
+            ["old", "*", "old"] => Operation::Square,
+            ["old", "*", operand] => Operation::Times(number(block, operand)?),
+            ["old", "+", operand] => Operation::Add(number(block, operand)?),
+            _ => {
+                return Err(ParseError::at(
+                    block,
+                    operation,
+                    "old * <n>, old + <n> or old * old",
+                ))
+            }
+        };
+        Ok(())
     }
-    fn parse_pass_false(&mut self, line: &String) {
-        let tokens: Vec<&str> = line.split_whitespace().collect();
-        self.pass_false = tokens[tokens.len() - 1].parse().unwrap();
+    // Where to throw to, checked against the number of monkeys. A monkey
+    // throwing to itself would keep catching the same item forever
+    fn parse_target(
+        block: &str,
+        target: &str,
+        index: usize,
+        num_monkeys: usize,
+    ) -> Result<usize, ParseError> {
+        match number(block, target)? {
+            monkey if monkey < num_monkeys && monkey != index => Ok(monkey),
+            _ => Err(ParseError::at(
+                block,
+                target,
+                format!(
+                    "a monkey from 0 to {} other than {}",
+                    num_monkeys - 1,
+                    index
+                ),
+            )),
+        }
     }
 
-    pub fn new(lines: &[String]) -> Self {
+    // The monkey at `index` of `num_monkeys`
+    pub fn parse(block: &str, index: usize, num_monkeys: usize) -> Result<Self, ParseError> {
         let mut me = Self {
             items: Vec::new(),
-            op: Operation::TIMES(4),
+            op: Operation::Times(4),
             test: 17,
             pass_true: 0,
             pass_false: 0,
             num_inspected: 0,
         };
-        me.parse_items(&lines[1]);
-        me.parse_operation(&lines[2]);
-        me.parse_test(&lines[3]);
-        me.parse_pass_true(&lines[4]);
-        me.parse_pass_false(&lines[5]);
-        me
+        let mut lines = block.lines();
+        field(block, lines.next(), "Monkey")?;
+        me.parse_items(block, field(block, lines.next(), "Starting items:")?)?;
+        me.parse_operation(block, field(block, lines.next(), "Operation: new =")?)?;
+        let test = field(block, lines.next(), "Test: divisible by")?;
+        me.test = number(block, test)?;
+        if me.test == 0 {
+            return Err(ParseError::at(block, test, "a number to divide by, not 0"));
+        }
+        let target = field(block, lines.next(), "If true: throw to monkey")?;
+        me.pass_true = Self::parse_target(block, target, index, num_monkeys)?;
+        let target = field(block, lines.next(), "If false: throw to monkey")?;
+        me.pass_false = Self::parse_target(block, target, index, num_monkeys)?;
+        Ok(me)
     }
     pub fn add_item(&mut self, item: i64) {
         self.items.push(item);
     }
-    pub fn toss(&mut self) -> Option<(usize, i64)> {
-        if self.items.len() == 0 {
+    // With relief, worry levels are divided by 3 after each inspection
+    pub fn toss(&mut self, relief: bool) -> Option<(usize, i64)> {
+        if self.items.is_empty() {
             None
         } else {
             self.num_inspected += 1;
             let mut item = self.items.pop().unwrap();
-            //println!(" starting Item: {}", item);
             item = match self.op {
-                Operation::TIMES(op2) => item * op2,
-                Operation::ADD(op2) => item + op2,
-                Operation::SQUARE => item * item,
+                Operation::Times(op2) => item * op2,
+                Operation::Add(op2) => item + op2,
+                Operation::Square => item * item,
             };
-            //println!(" after op: {}", item);
-            //item = item / 3;
-            //println!(" after div: {}", item);
+            if relief {
+                item /= 3;
+            }
             let dest = if (item % self.test as i64) == 0 {
                 self.pass_true
             } else {
@@ -94,3 +129,81 @@ impl Monkey {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct Puzzle {
+    monkeys: Vec<Monkey>,
+}
+
+impl Puzzle {
+    fn monkey_business(&self, rounds: u32, relief: bool) -> u64 {
+        let mut monkeys = self.monkeys.clone();
+        // Keeps worry levels small without changing any monkey's test
+        let common_divisor: i64 = monkeys.iter().map(|m| m.test as i64).product();
+
+        for _ in 0..rounds {
+            for index in 0..monkeys.len() {
+                while let Some((dest, item)) = monkeys[index].toss(relief) {
+                    monkeys[dest].add_item(item % common_divisor);
+                }
+            }
+        }
+        let mut inspected: Vec<u64> = monkeys.iter().map(|m| m.num_inspected as u64).collect();
+        inspected.sort_unstable_by(|a, b| b.cmp(a));
+        inspected[0] * inspected[1]
+    }
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let blocks = blocks(input);
+        let monkeys = blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| {
+                Monkey::parse(block, index, blocks.len()).map_err(|err| err.within(input, block))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { monkeys })
+    }
+    fn part1(&self) -> Answer {
+        self.monkey_business(20, true).into()
+    }
+    fn part2(&self) -> Answer {
+        self.monkey_business(10_000, false).into()
+    }
+}
+
+impl Generate for Puzzle {
+    // Eight monkeys sharing `size` items. Each tests a different prime, so
+    // worry levels kept below their product stay small enough to square
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut tests = [2, 3, 5, 7, 11, 13, 17, 19];
+        rng.shuffle(&mut tests);
+        let mut items: Vec<Vec<String>> = vec![Vec::new(); tests.len()];
+        for _ in 0..size.max(1) {
+            items[rng.below(tests.len())].push(rng.range(50..100).to_string());
+        }
+        let monkeys: Vec<String> = (0..tests.len())
+            .map(|monkey| {
+                let operation = match rng.below(3) {
+                    0 => "old * old".to_string(),
+                    1 => format!("old * {}", rng.range(2..20)),
+                    _ => format!("old + {}", rng.range(1..9)),
+                };
+                let mut target = || (monkey + 1 + rng.below(tests.len() - 1)) % tests.len();
+                let (pass_true, pass_false) = (target(), target());
+                format!(
+                    "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+                    monkey,
+                    items[monkey].join(", "),
+                    operation,
+                    tests[monkey],
+                    pass_true,
+                    pass_false
+                )
+            })
+            .collect();
+        monkeys.join("\n")
+    }
+}
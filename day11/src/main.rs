@@ -1,56 +1,5 @@
-use std::{cell::RefCell, rc::Rc};
-use std::{
-    env,
-    fs::File,
-    io::{self, BufRead, BufReader},
-    path::Path,
-};
-
-use day11::Monkey;
-
-fn lines_from_file(filename: impl AsRef<Path>) -> io::Result<Vec<String>> {
-    BufReader::new(File::open(filename)?).lines().collect()
-}
+use day11::Puzzle;
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let filename = &args[1];
-    let lines = lines_from_file(filename).expect("Could not load");
-
-    let mut starting_line = 0;
-    let mut monkeys: Vec<Rc<RefCell<Monkey>>> = Vec::new();
-
-    while starting_line < lines.len() {
-        monkeys.push(Rc::new(RefCell::new(Monkey::new(
-            &lines[starting_line..starting_line + 6],
-        ))));
-        starting_line += 7;
-    }
-    let mut common_divisor: i64 = 1;
-    for monkey in &monkeys {
-        common_divisor *= monkey.borrow().test as i64;
-    }
-    for round in 1..=10_000 {
-        for monkey in &monkeys {
-            let monkey: &mut Monkey = &mut monkey.borrow_mut();
-            let mut done = false;
-            while !done {
-                match monkey.toss() {
-                    None => done = true,
-                    Some((dest, item)) => {
-                        let mut dest_monkey = monkeys[dest].borrow_mut();
-                        dest_monkey.add_item(item % common_divisor)
-                    }
-                }
-            }
-        }
-
-        println!("After round {}", round);
-        for monkey in &monkeys {
-            let monkey = monkey.borrow();
-            println!("Monkey {}", monkey.num_inspected);
-        }
-    }
-    //println!("{:?}", monkeys);
+    aoc_common::run::<Puzzle>(11);
 }
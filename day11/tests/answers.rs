@@ -0,0 +1,42 @@
+use aoc_common::{check, check_generated, check_input, Solution};
+use day11::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 10_605, 2_713_310_158_i64);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 99_840, 20_683_044_837_i64);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(20, 3);
+}
+
+#[test]
+fn rejects_bad_monkeys() {
+    let monkey = |test: u32, pass_true: usize, pass_false: usize| {
+        format!(
+            "  Starting items: 79\n  Operation: new = old * 19\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}\n",
+            test, pass_true, pass_false
+        )
+    };
+    let troop = |monkeys: &[String]| {
+        let monkeys: Vec<String> = monkeys
+            .iter()
+            .enumerate()
+            .map(|(index, monkey)| format!("Monkey {}:\n{}", index, monkey))
+            .collect();
+        monkeys.join("\n")
+    };
+    assert!(Puzzle::parse(&troop(&[monkey(2, 1, 1), monkey(3, 0, 0)])).is_ok());
+    // Throwing to itself
+    let err = Puzzle::parse(&troop(&[monkey(2, 1, 1), monkey(3, 0, 1)])).unwrap_err();
+    assert_eq!((err.line, err.column), (13, 31));
+    // Dividing by 0
+    let err = Puzzle::parse(&troop(&[monkey(0, 1, 1), monkey(3, 0, 0)])).unwrap_err();
+    assert_eq!((err.line, err.column), (4, 22));
+}
@@ -0,0 +1,10 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day12::HeightMap;
+
+fn solve(c: &mut Criterion) {
+    bench::<HeightMap>(c, "day12", &["sample.txt", "input.txt"]);
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
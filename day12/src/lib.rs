@@ -1,130 +1,142 @@
-use pathfinding::prelude::astar;
-
-#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
-struct Point {
-    x: i32,
-    y: i32,
-}
+use aoc_common::{
+    astar, heat, Answer, Colour, Draw, Generate, Grid, Image, ParseError, Path, Point, Rng,
+    Solution,
+};
+use tracing::debug;
 
 #[derive(Debug)]
 pub struct HeightMap {
-    grid: Vec<Vec<i32>>,
-    starting_pos: Point,
-    target_pos: Point,
+    grid: Grid<i32>,
+    starting_pos: Point<usize>,
+    target_pos: Point<usize>,
 }
 
-impl Point {
-    fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
+impl HeightMap {
+    fn successors(&self, pt: &Point<usize>) -> Vec<(Point<usize>, u32)> {
+        let max_height = self.grid[*pt] + 1;
+        self.grid
+            .neighbours4(*pt)
+            .filter(|&next| self.grid[next] <= max_height)
+            .map(|next| (next, 1))
+            .collect()
+    }
+    // Searches from all the starts at once, so the path found is the
+    // shortest from whichever of them is closest
+    fn shortest_from(
+        &self,
+        starts: impl IntoIterator<Item = Point<usize>>,
+    ) -> Option<Path<Point<usize>, u32>> {
+        let goal = self.target_pos;
+        astar(
+            starts,
+            |p| self.successors(p),
+            |p| p.manhattan(goal) as u32 / 3,
+            |p| *p == goal,
+        )
+    }
+    // Every square at the lowest height, a possible start in part 2
+    fn lowest(&self) -> Vec<Point<usize>> {
+        let poss_starting: Vec<Point<usize>> = self
+            .grid
+            .iter()
+            .filter(|(_, &height)| height == 0)
+            .map(|(pt, _)| pt)
+            .collect();
+        debug!(count = poss_starting.len(), "possible starting points");
+        poss_starting
+    }
+    pub fn find_path(&self) -> Option<u32> {
+        self.shortest_from([self.starting_pos])
+            .map(|path| path.cost)
     }
 
-    fn distance(&self, other: &Point) -> u32 {
-        (self.x.abs_diff(other.x) + self.y.abs_diff(other.y)) as u32
+    pub fn find_path_pt2(&self) -> Option<u32> {
+        self.shortest_from(self.lowest()).map(|path| path.cost)
     }
 }
 
-impl HeightMap {
-    pub fn new() -> Self {
-        Self {
-            grid: Vec::new(),
-            starting_pos: Point { x: 0, y: 0 },
-            target_pos: Point { x: 0, y: 0 },
+impl Draw for HeightMap {
+    // The heights from low and dark to high and bright, with the part's
+    // path in cyan and the summit in white
+    fn image(&self, part: u8) -> Image {
+        let mut image = Image::from_grid(&self.grid, |&height| heat(height as f64, 25.0));
+        let path = match part {
+            1 => self.shortest_from([self.starting_pos]),
+            _ => self.shortest_from(self.lowest()),
+        };
+        for &pt in path.iter().flat_map(|path| &path.nodes) {
+            image[pt] = Colour::Cyan.rgb();
         }
+        image[self.target_pos] = Colour::White.rgb();
+        image
     }
-    pub fn add_line(&mut self, line: String) {
-        let row = self.grid.len() as i32;
-        let mut new_line: Vec<i32> = Vec::new();
-        let mut col = 0;
+}
 
-        for ch in line.chars() {
-            match ch {
-                'S' => {
-                    self.starting_pos.x = col;
-                    self.starting_pos.y = row;
-                    new_line.push(0);
-                }
-                'E' => {
-                    self.target_pos.x = col;
-                    self.target_pos.y = row;
-                    new_line.push(25);
-                }
-                'a'..='z' => new_line.push(ch as i32 - 'a' as i32),
-                _ => panic!("Unknown character"),
-            }
-            col += 1;
-        }
-        self.grid.push(new_line);
+impl Solution for HeightMap {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let map = Grid::parse(input, "a height a-z, S or E", |ch| {
+            matches!(ch, 'a'..='z' | 'S' | 'E').then_some(ch)
+        })?;
+        let find = |mark: char| {
+            map.position(|&ch| ch == mark)
+                .ok_or_else(|| ParseError::at_end(input, format!("a square marked {}", mark)))
+        };
+        let starting_pos = find('S')?;
+        let target_pos = find('E')?;
+        let grid = map.map(|&ch| match ch {
+            'S' => 0,
+            'E' => 25,
+            _ => ch as i32 - 'a' as i32,
+        });
+        Ok(HeightMap {
+            grid,
+            starting_pos,
+            target_pos,
+        })
     }
-    fn successors(&self, pt: &Point) -> Vec<(Point, u32)> {
-        let mut neighbors: Vec<(Point, u32)> = Vec::new();
-
-        let max_height = self.grid[pt.y as usize][pt.x as usize] + 1;
-        let max_y = self.grid.len() as i32 - 1;
-        let max_x = self.grid[0].len() as i32 - 1;
-
-        let mut new_points: Vec<Point> = Vec::new();
-
-        if pt.x != 0 {
-            new_points.push(Point::new(pt.x - 1, pt.y));
-        }
-        if pt.x != max_x {
-            new_points.push(Point::new(pt.x + 1, pt.y));
-        }
-        if pt.y != 0 {
-            new_points.push(Point::new(pt.x, pt.y - 1));
-        }
-        if pt.y != max_y {
-            new_points.push(Point::new(pt.x, pt.y + 1));
-        }
-        for pt in new_points {
-            if self.grid[pt.y as usize][pt.x as usize] <= max_height {
-                neighbors.push((pt, 1));
-            }
-        }
-        return neighbors;
+    fn part1(&self) -> Answer {
+        self.find_path().map_or(Answer::None, Answer::from)
     }
-    pub fn find_path(&self) {
-        let goal: Point = self.target_pos;
-        let result = astar(
-            &self.starting_pos,
-            |p| self.successors(p),
-            |p| p.distance(&goal) / 3,
-            |p| *p == goal,
-        );
-        match result {
-            None => println!("No path found :-("),
-            Some((path, cost)) => println!("Path: {:?} cost: {}", path, cost),
-        }
+    fn part2(&self) -> Answer {
+        self.find_path_pt2().map_or(Answer::None, Answer::from)
     }
+}
 
-    pub fn find_path_pt2(&self) {
-        let mut poss_starting: Vec<Point> = Vec::new();
-        for y in 0..self.grid.len() {
-            for x in 0..self.grid[y as usize].len() {
-                if self.grid[y][x] == 0 {
-                    poss_starting.push(Point::new(x as i32, y as i32))
-                }
+impl Generate for HeightMap {
+    // A map `size` squares a side, at least 6. Its heights are laid along a
+    // path snaking row by row that never climbs more than one at a step,
+    // rising to z and falling away again, so the best signal is always in
+    // reach of the start
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let side = size.max(6);
+        let mut heights = Vec::with_capacity(side * side);
+        let (mut height, mut climbing) = (0, true);
+        for _ in 0..side * side {
+            heights.push(height);
+            if climbing {
+                height += 1;
+                climbing = height < 25;
+            } else {
+                height = (height - rng.range(0..3)).max(0);
+                climbing = height <= rng.range(0..20);
             }
         }
-        println!("Possible starting points: {:?}", poss_starting.len());
-        let mut shortest = u32::MAX;
-        let goal: Point = self.target_pos;
-        for pt in poss_starting {
-            let result = astar(
-                &pt,
-                |p| self.successors(p),
-                |p| p.distance(&goal) / 3,
-                |p| *p == goal,
-            );
-            match result {
-                None => {}
-                Some((_path, cost)) => {
-                    if cost < shortest {
-                        shortest = cost;
-                    }
-                }
-            }
+        let peaks: Vec<usize> = (0..heights.len()).filter(|&i| heights[i] == 25).collect();
+        let best = *rng.pick(&peaks);
+        let mut rows: Vec<Vec<u8>> = heights
+            .chunks(side)
+            .map(|row| row.iter().map(|&height| b'a' + height as u8).collect())
+            .collect();
+        // Every other row runs right to left
+        for row in rows.iter_mut().skip(1).step_by(2) {
+            row.reverse();
         }
-        println!("Shortest found: {}", shortest);
+        rows[0][0] = b'S';
+        let (x, y) = (best % side, best / side);
+        let x = if y % 2 == 1 { side - 1 - x } else { x };
+        rows[y][x] = b'E';
+        rows.iter()
+            .map(|row| String::from_utf8_lossy(row) + "\n")
+            .collect()
     }
 }
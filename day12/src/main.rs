@@ -1,25 +1,5 @@
-use std::{
-    env,
-    fs::File,
-    io::{self, BufRead, BufReader},
-    path::Path,
-};
-
 use day12::HeightMap;
 
-fn lines_from_file(filename: impl AsRef<Path>) -> io::Result<Vec<String>> {
-    BufReader::new(File::open(filename)?).lines().collect()
-}
-
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let filename = &args[1];
-    let lines = lines_from_file(filename).expect("Could not load");
-    let mut height_map = HeightMap::new();
-
-    for line in lines {
-        height_map.add_line(line);
-    }
-    height_map.find_path_pt2();
+    aoc_common::run::<HeightMap>(12);
 }
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day12::HeightMap;
+
+#[test]
+fn sample() {
+    check::<HeightMap>("sample.txt", 31, 29);
+}
+
+#[test]
+fn input() {
+    check_input::<HeightMap>("input.txt", 468, 459);
+}
+
+#[test]
+fn generated() {
+    check_generated::<HeightMap>(20, 3);
+}
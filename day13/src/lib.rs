@@ -1,42 +1,179 @@
+use aoc_common::{blocks, Answer, Generate, ParseError, Rng, Solution};
 use nom::{
-    branch::alt, character::complete, combinator::map, multi::separated_list0, sequence::delimited,
+    branch::alt,
+    character::complete,
+    combinator::{cut, map},
+    error::{Error, ErrorKind},
+    multi::separated_list0,
+    sequence::{preceded, terminated},
     IResult,
 };
-use std::cmp::Ordering;
+use std::{cmp::Ordering, fmt};
 
-#[derive(Debug, Clone, PartialEq, Eq, Ord)]
+#[derive(Debug, Clone)]
 pub enum Packet {
     Int(u32),
     List(Vec<Packet>),
 }
 
+// Far deeper than any packet in the puzzle, and shallow enough that parsing
+// and comparing packets can't run out of stack
+const MAX_DEPTH: usize = 100;
+
 impl Packet {
-    pub fn new(input: &str) -> Self {
-        fn parse(line: &str) -> IResult<&str, Packet> {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        fn parse(line: &str, depth: usize) -> IResult<&str, Packet> {
+            if depth > MAX_DEPTH {
+                return Err(nom::Err::Failure(Error::new(line, ErrorKind::TooLarge)));
+            }
+            // Once a list is opened, report errors inside it rather than at its start
             alt((
-                delimited(
+                preceded(
                     complete::char('['),
-                    map(separated_list0(complete::char(','), parse), Packet::List),
-                    complete::char(']'),
+                    cut(terminated(
+                        map(
+                            separated_list0(complete::char(','), |line| parse(line, depth + 1)),
+                            Packet::List,
+                        ),
+                        complete::char(']'),
+                    )),
                 ),
                 map(complete::u32, Packet::Int),
             ))(line)
         }
-        let result = parse(input);
-        result.unwrap().1
+        match parse(input, 0) {
+            Ok(("", packet)) => Ok(packet),
+            Ok((rest, _)) => Err(ParseError::at(input, rest, "end of packet")),
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                let expected = match err.code {
+                    ErrorKind::TooLarge => format!("lists nested at most {} deep", MAX_DEPTH),
+                    _ => "a number, a list or ]".to_string(),
+                };
+                Err(ParseError::at(input, err.input, expected))
+            }
+            Err(nom::Err::Incomplete(_)) => Err(ParseError::at_end(input, "a number, a list or ]")),
+        }
+    }
+}
+
+// Written the way the input writes it, so a packet parses back from its text
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Packet::Int(value) => write!(f, "{}", value),
+            Packet::List(packets) => {
+                write!(f, "[")?;
+                for (index, packet) in packets.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", packet)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Packet::Int(l), Packet::Int(r)) => l.cmp(r),
+            (Packet::List(l), Packet::List(r)) => l.cmp(r),
+            // A lone integer compares as a list holding just that integer
+            (Packet::Int(l), Packet::List(_)) => Packet::List(vec![Packet::Int(*l)]).cmp(other),
+            (Packet::List(_), Packet::Int(r)) => self.cmp(&Packet::List(vec![Packet::Int(*r)])),
+        }
     }
 }
 
 impl PartialOrd for Packet {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        match (self, other) {
-            (Packet::Int(l), Packet::Int(r)) => l.partial_cmp(r),
-            (Packet::List(l), Packet::List(r)) => l.partial_cmp(r),
-            (l, Packet::List(r)) => match &**r {
-                [r, ..] if l != r => l.partial_cmp(r),
-                _ => 1usize.partial_cmp(&r.len()),
-            },
-            (Packet::List(_), Packet::Int(_)) => other.partial_cmp(self).map(Ordering::reverse),
+        Some(self.cmp(other))
+    }
+}
+
+// Equality has to agree with the ordering, so `2` and `[2]` are equal packets
+impl PartialEq for Packet {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Packet {}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    packets: Vec<Packet>,
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut packets = Vec::new();
+        // Packets come in pairs, one block per pair
+        for block in blocks(input) {
+            let lines: Vec<&str> = block.lines().collect();
+            if lines.len() != 2 {
+                return Err(ParseError::at(input, block, "a pair of packets"));
+            }
+            for line in lines {
+                packets.push(Packet::new(line).map_err(|err| err.within(input, line))?);
+            }
         }
+        Ok(Self { packets })
+    }
+    fn part1(&self) -> Answer {
+        self.packets
+            .chunks(2)
+            .enumerate()
+            .filter(|(_, pair)| pair[0] < pair[1])
+            .map(|(index, _)| index + 1)
+            .sum::<usize>()
+            .into()
+    }
+    fn part2(&self) -> Answer {
+        let divider1 = Packet::new("[[2]]").unwrap();
+        let divider2 = Packet::new("[[6]]").unwrap();
+
+        let mut packets = self.packets.clone();
+        packets.push(divider1.clone());
+        packets.push(divider2.clone());
+        packets.sort();
+
+        let index1 = packets.iter().position(|p| *p == divider1).unwrap() + 1;
+        let index2 = packets.iter().position(|p| *p == divider2).unwrap() + 1;
+        (index1 * index2).into()
+    }
+}
+
+// A packet nested at most `depth` deeper
+fn random_packet(rng: &mut Rng, depth: usize) -> Packet {
+    if depth == 0 || rng.chance(40) {
+        Packet::Int(rng.range(0..11) as u32)
+    } else {
+        Packet::List(
+            (0..rng.below(5))
+                .map(|_| random_packet(rng, depth - 1))
+                .collect(),
+        )
+    }
+}
+
+impl Generate for Puzzle {
+    // `size` pairs of packets
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let pairs: Vec<String> = (0..size.max(1))
+            .map(|_| {
+                let mut pair = [random_packet(rng, 4), random_packet(rng, 4)];
+                // Outermost is always a list
+                for packet in pair.iter_mut() {
+                    if let Packet::Int(_) = packet {
+                        *packet = Packet::List(vec![packet.clone()]);
+                    }
+                }
+                format!("{}\n{}\n", pair[0], pair[1])
+            })
+            .collect();
+        pairs.join("\n")
     }
 }
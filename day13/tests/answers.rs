@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day13::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 13, 140);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 6187, 23_520);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(30, 3);
+}
@@ -0,0 +1,41 @@
+use std::cmp::Ordering;
+
+use day13::Packet;
+use proptest::prelude::*;
+
+fn packet() -> impl Strategy<Value = Packet> {
+    let int = (0..12_u32).prop_map(Packet::Int);
+    int.prop_recursive(4, 32, 4, |inner| {
+        prop::collection::vec(inner, 0..4).prop_map(Packet::List)
+    })
+}
+
+// Equal packets can be written differently, 2 and [2] say, so compare the text
+// to check a packet comes back whole
+fn same(left: &Packet, right: &Packet) -> bool {
+    left.to_string() == right.to_string()
+}
+
+proptest! {
+    #[test]
+    fn packets_round_trip(packet in packet()) {
+        let parsed = Packet::new(&packet.to_string()).unwrap();
+        prop_assert!(same(&parsed, &packet));
+    }
+
+    #[test]
+    fn ordering_is_total(a in packet(), b in packet(), c in packet()) {
+        prop_assert_eq!(a.cmp(&a), Ordering::Equal);
+        prop_assert_eq!(a.cmp(&b), b.cmp(&a).reverse());
+        if a <= b && b <= c {
+            prop_assert!(a <= c);
+        }
+        // Sorting any way round puts the packets in the same order
+        let mut forwards = [a.clone(), b.clone(), c.clone()];
+        let mut backwards = [c, b, a];
+        forwards.sort();
+        backwards.sort();
+        prop_assert!(forwards.windows(2).all(|pair| pair[0] <= pair[1]));
+        prop_assert!(forwards.iter().zip(&backwards).all(|(x, y)| x == y));
+    }
+}
@@ -0,0 +1,10 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day14::Cave;
+
+fn solve(c: &mut Criterion) {
+    bench::<Cave>(c, "day14", &["sample.txt", "input.txt"]);
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
@@ -1,154 +1,250 @@
+use aoc_common::{
+    parse_lines, Animate, Answer, Colour, Dir8, Draw, Frame, Generate, Image, ParseError, Point,
+    Rng, Solution, SparseGrid,
+};
 use nom::{
-    bytes::complete::tag,
     character::complete::{char, digit1},
     combinator::{map, map_res},
-    multi::separated_list1,
     sequence::separated_pair,
     IResult,
 };
-use std::cmp::{max, min};
-use std::collections::HashMap;
 use std::str::FromStr;
 
-#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
-struct Point {
-    x: u32,
-    y: u32,
-}
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Material {
     Rock,
     Sand,
 }
 
+#[derive(Clone)]
 pub struct Cave {
-    map: HashMap<Point, Material>,
-    top_left: Point,
-    bottom_right: Point,
+    map: SparseGrid<Material>,
+    // Depth of the lowest rock
+    lowest: i64,
+    floor: bool,
 }
 
-fn parse_numbers(input: &str) -> IResult<&str, u32> {
-    map_res(digit1, u32::from_str)(input)
+// Where the sand pours in
+const SOURCE: Point<i64> = Point::new(500, 0);
+
+// Every rock is drawn into the map, so a path running off thousands of squares
+// away would fill memory. Real scans stay within a few hundred of the source.
+const MAX_COORD: i64 = 1000;
+
+fn parse_numbers(input: &str) -> IResult<&str, i64> {
+    map_res(digit1, i64::from_str)(input)
 }
 
-fn range_inclusive(a: u32, b: u32) -> impl Iterator<Item = u32> {
-    let x: Box<dyn Iterator<Item = u32>>;
-    if b > a {
-        x = Box::new(a..=b)
-    } else {
-        x = Box::new((b..=a).rev())
-    }
-    x
+fn parse_point(input: &str) -> IResult<&str, Point<i64>> {
+    let parse_two_numbers = separated_pair(parse_numbers, char(','), parse_numbers);
+    map(parse_two_numbers, |(x, y)| Point::new(x, y))(input)
 }
 
-impl Point {
-    pub fn new(x: u32, y: u32) -> Self {
-        Self { x, y }
-    }
-    fn parse(input: &str) -> IResult<&str, Self> {
-        let parse_two_numbers = separated_pair(parse_numbers, char(','), parse_numbers);
-        map(parse_two_numbers, |(x, y)| Point { x, y })(input)
+impl Default for Cave {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Cave {
     pub fn new() -> Self {
+        Self::with_floor(true)
+    }
+    // Without a floor, sand falls into the abyss below the lowest rock
+    pub fn with_floor(floor: bool) -> Self {
         Self {
-            map: HashMap::new(),
-            top_left: Point::new(u32::MAX, u32::MAX),
-            bottom_right: Point::new(0, 0),
+            map: SparseGrid::new(),
+            lowest: 0,
+            floor,
         }
     }
-    pub fn add_line(&mut self, input: &str) {
-        let result = separated_list1(tag(" -> "), Point::parse)(input);
-        // go through result and add to hashmap
-        let points = result.unwrap().1;
-        let mut index: usize = 1;
-        while index < points.len() {
-            let start_x = points[index - 1].x;
-            let start_y = points[index - 1].y;
-            let end_x = points[index].x;
-            let end_y = points[index].y;
-
-            self.top_left.y = min(self.top_left.y, min(start_y, end_y));
-            self.top_left.x = min(self.top_left.x, min(start_x, end_x));
-            self.bottom_right.y = max(self.bottom_right.y, max(start_y, end_y));
-            self.bottom_right.x = max(self.bottom_right.x, max(start_x, end_x));
-
-            for x in range_inclusive(start_x, end_x) {
-                self.map.insert(Point::new(x, start_y), Material::Rock);
+    pub fn add_line(&mut self, input: &str) -> Result<(), ParseError> {
+        let mut points: Vec<Point<i64>> = Vec::new();
+        for token in input.split(" -> ") {
+            let point = match parse_point(token) {
+                Ok(("", point)) => point,
+                Ok((rest, _)) => return Err(ParseError::at(input, rest, "\" -> \"")),
+                Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                    return Err(ParseError::at(input, err.input, "a point like 498,4"));
+                }
+                Err(nom::Err::Incomplete(_)) => {
+                    return Err(ParseError::at_end(input, "a point like 498,4"));
+                }
+            };
+            if point.x > MAX_COORD || point.y > MAX_COORD {
+                let expected = format!("a point within {},{}", MAX_COORD, MAX_COORD);
+                return Err(ParseError::at(input, token, expected));
             }
-            for y in range_inclusive(start_y, end_y) {
-                self.map.insert(Point::new(start_x, y), Material::Rock);
+            // Paths of rock only run straight across or down
+            if let Some(last) = points.last() {
+                if last.x != point.x && last.y != point.y {
+                    return Err(ParseError::at(
+                        input,
+                        token,
+                        "a point in line with the last",
+                    ));
+                }
             }
-            index += 1;
+            points.push(point);
         }
+        for pair in points.windows(2) {
+            let (start, end) = (pair[0], pair[1]);
+            for x in start.x.min(end.x)..=start.x.max(end.x) {
+                for y in start.y.min(end.y)..=start.y.max(end.y) {
+                    self.map.insert(Point::new(x, y), Material::Rock);
+                }
+            }
+        }
+        // A path of a single point is still rock
+        for point in &points {
+            self.map.insert(*point, Material::Rock);
+            self.lowest = self.lowest.max(point.y);
+        }
+        Ok(())
     }
-    fn get(&self, pt: &Point) -> Option<&Material> {
-        if pt.y == self.bottom_right.y + 2 {
+    fn get(&self, pt: &Point<i64>) -> Option<&Material> {
+        if self.floor && pt.y == self.lowest + 2 {
             Some(&Material::Rock)
         } else {
-            self.map.get(pt)
+            self.map.get(*pt)
         }
     }
     // True if the sand found a resting spot
     pub fn drop_sand(&mut self) -> bool {
-        // For now always drops from 500, 0
-        let mut sand_pos = Point::new(500, 0);
+        let mut sand_pos = SOURCE;
 
         loop {
-            match self.get(&Point::new(sand_pos.x, sand_pos.y + 1)) {
-                None => sand_pos.y += 1,
-                Some(_) => match self.get(&Point::new(sand_pos.x - 1, sand_pos.y + 1)) {
-                    None => {
-                        sand_pos.x -= 1;
-                        sand_pos.y += 1;
+            if !self.floor && sand_pos.y > self.lowest {
+                return false;
+            }
+            // Straight down, else down to the left, else down to the right
+            let next = [Dir8::S, Dir8::SW, Dir8::SE]
+                .iter()
+                .map(|dir| sand_pos + dir.offset())
+                .find(|next| self.get(next).is_none());
+            match next {
+                Some(next) => sand_pos = next,
+                None => {
+                    if sand_pos.y == 0 {
+                        return false;
                     }
-                    Some(_) => match self.get(&Point::new(sand_pos.x + 1, sand_pos.y + 1)) {
-                        None => {
-                            sand_pos.x += 1;
-                            sand_pos.y += 1;
-                        }
-                        Some(_) => {
-                            if sand_pos.y == 0 {
-                                return false;
-                            }
-                            self.map.insert(sand_pos, Material::Sand);
-                            return true;
-                        }
-                    },
-                },
+                    self.map.insert(sand_pos, Material::Sand);
+                    return true;
+                }
             }
         }
     }
-    pub fn draw(&self) {
-        for y in self.top_left.y..=self.bottom_right.y {
-            let mut row = String::from("");
-            for x in self.top_left.x..=self.bottom_right.x {
-                row.push(match self.map.get(&Point::new(x, y)) {
-                    Some(material) => match material {
-                        Material::Rock => '#',
-                        Material::Sand => 'o',
-                    },
-                    None => '.',
-                });
-            }
-            println!("{} {}", y, row);
+    // The cave as far as rock and sand reach, with the floor when there is one
+    pub fn frame(&self, caption: impl Into<String>) -> Frame {
+        let (mut min, mut max) = self.map.bounds().unwrap_or((SOURCE, SOURCE));
+        min = Point::new(min.x.min(SOURCE.x), min.y.min(SOURCE.y));
+        max.x = max.x.max(SOURCE.x);
+        if self.floor {
+            max.y = self.lowest + 1;
+        }
+        let cells = |cell: Option<&Material>| match cell {
+            Some(Material::Rock) => ('#', Colour::Grey),
+            Some(Material::Sand) => ('o', Colour::Yellow),
+            None => (' ', Colour::Plain),
+        };
+        let source = SOURCE - min;
+        let mut frame = Frame::from_sparse(&self.map, (min, max), caption, cells)
+            .with_focus(Point::new(source.x as usize, source.y as usize));
+        if self.floor {
+            let width = frame.width();
+            frame.push_row(std::iter::repeat_n(('#', Colour::Grey), width));
         }
+        frame
     }
 }
-/*
-    fn parse(line: &str) -> IResult<&str, Packet> {
-        alt((
-            delimited(
-                complete::char('['),
-                map(separated_list0(complete::char(','), parse), Packet::List),
-                complete::char(']'),
-            ),
-            map(complete::u32, Packet::Int),
-        ))(line)
+
+impl Cave {
+    // The cave once the source is blocked or sand starts falling into the
+    // abyss, and the number of grains that came to rest
+    fn fill_with_sand(&self, floor: bool) -> (Cave, u32) {
+        let mut cave = self.clone();
+        cave.floor = floor;
+
+        let mut total_sand = 0;
+        while cave.drop_sand() {
+            total_sand += 1;
+        }
+        if floor {
+            // The last grain comes to rest on the source itself
+            cave.map.insert(SOURCE, Material::Sand);
+            total_sand += 1;
+        }
+        (cave, total_sand)
+    }
+}
+
+impl Animate for Cave {
+    // A frame for each grain of sand as it comes to rest
+    fn frames(&self, part: u8) -> Box<dyn Iterator<Item = Frame> + '_> {
+        let mut cave = self.clone();
+        cave.floor = part == 2;
+        let mut grains = 0;
+        let mut done = false;
+        Box::new(std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            done = !cave.drop_sand();
+            if !done {
+                grains += 1;
+            } else if cave.floor {
+                // The last grain comes to rest on the source itself
+                cave.map.insert(SOURCE, Material::Sand);
+                grains += 1;
+            }
+            Some(cave.frame(format!("Day 14 part {}: {} grains at rest", part, grains)))
+        }))
+    }
+}
+
+impl Draw for Cave {
+    // The sand pile once it's finished growing
+    fn image(&self, part: u8) -> Image {
+        let (cave, _) = self.fill_with_sand(part == 2);
+        Image::from_frame(&cave.frame(""))
+    }
+}
+
+impl Solution for Cave {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut cave = Cave::new();
+        parse_lines(input, |line| cave.add_line(line))?;
+        Ok(cave)
+    }
+    fn part1(&self) -> Answer {
+        self.fill_with_sand(false).1.into()
+    }
+    fn part2(&self) -> Answer {
+        self.fill_with_sand(true).1.into()
+    }
+}
+
+impl Generate for Cave {
+    // `size` paths of rock, spreading wider and deeper as there are more of
+    // them but never further than the scan can reach
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let spread = (20 + size as i64).min(MAX_COORD / 2 - 10);
+        let depth = (20 + size as i64).min(MAX_COORD - 10);
+        (0..size.max(1))
+            .map(|_| {
+                let mut pt = Point::new(SOURCE.x + rng.range(-spread..spread), rng.range(2..depth));
+                let mut points = vec![format!("{},{}", pt.x, pt.y)];
+                for turn in 0..rng.range(1..5) {
+                    let step = rng.range(1..10) * *rng.pick(&[-1, 1]);
+                    if turn % 2 == 0 {
+                        pt.x = (pt.x + step).clamp(SOURCE.x - spread, SOURCE.x + spread);
+                    } else {
+                        pt.y = (pt.y + step).clamp(2, depth);
+                    }
+                    points.push(format!("{},{}", pt.x, pt.y));
+                }
+                points.join(" -> ") + "\n"
+            })
+            .collect()
     }
-    let result = parse(input);
-    result.unwrap().1
 }
-*/
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day14::Cave;
+
+#[test]
+fn sample() {
+    check::<Cave>("sample.txt", 24, 93);
+}
+
+#[test]
+fn input() {
+    check_input::<Cave>("input.txt", 838, 27_539);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Cave>(30, 3);
+}
@@ -0,0 +1,16 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day15::SensorNetwork;
+
+fn solve(c: &mut Criterion) {
+    bench::<SensorNetwork>(c, "day15", &["sample.txt", "input.txt"]);
+}
+
+// Part 2 scans four million rows of the real input, nearly 3s a solve, so
+// take fewer samples
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solve
+}
+criterion_main!(benches);
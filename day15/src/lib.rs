@@ -1,44 +1,34 @@
+use aoc_common::{number, parse_lines, Answer, Generate, ParseError, Point, Rng, Solution};
+use rayon::prelude::*;
+use regex::Regex;
 use std::{
     cmp::{max, min},
     collections::{HashMap, HashSet},
     ops::RangeInclusive,
 };
+use tracing::debug;
 
 #[derive(Debug)]
 struct Sensor {
-    pt: Point,
-    nearest_beacon: Point,
+    pt: Point<i32>,
+    nearest_beacon: Point<i32>,
     distance: i32,
 }
 
 #[derive(Debug)]
 pub struct SensorNetwork {
     sensors: Vec<Sensor>,
-    beacons: HashMap<Point, bool>,
-    top_left: Point,
-    bottom_right: Point,
+    beacons: HashMap<Point<i32>, bool>,
+    top_left: Point<i32>,
+    bottom_right: Point<i32>,
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-
-impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
-    }
-    pub fn distance(&self, other: &Self) -> i32 {
-        (self.x.abs_diff(other.x) + self.y.abs_diff(other.y)) as i32
-    }
-}
 impl Sensor {
-    pub fn new(sensor_pt: Point, beacon_pt: Point) -> Self {
+    pub fn new(sensor_pt: Point<i32>, beacon_pt: Point<i32>) -> Self {
         Self {
             pt: sensor_pt,
             nearest_beacon: beacon_pt,
-            distance: sensor_pt.distance(&beacon_pt),
+            distance: sensor_pt.manhattan(beacon_pt),
         }
     }
 
@@ -52,45 +42,24 @@ impl Sensor {
     }
 }
 
-fn join_range(ranges: &[RangeInclusive<i32>]) -> (RangeInclusive<i32>, Vec<RangeInclusive<i32>>) {
-    let mut indexes = (1..ranges.len()).collect::<HashSet<usize>>();
-    let mut range = ranges[0].to_owned();
-    loop {
-        let mut indexes_to_remove: Vec<usize> = Vec::new();
-        for index in indexes.iter() {
-            let curr_range = &ranges[*index];
-            if range.contains(curr_range.start()) {
-                indexes_to_remove.push(*index);
-                if !range.contains(curr_range.end()) {
-                    let new_range = *range.start()..=*curr_range.end();
-                    range = new_range;
-                }
-            } else if curr_range.contains(range.start()) {
-                indexes_to_remove.push(*index);
-                if !curr_range.contains(range.end()) {
-                    let new_range = *curr_range.start()..=*range.end();
-                    range = new_range;
-                } else {
-                    range = curr_range.to_owned();
-                }
-            }
-        }
-        if indexes_to_remove.is_empty() {
-            let mut remainder = indexes
-                .iter()
-                .map(|i| ranges[*i].to_owned())
-                .collect::<Vec<RangeInclusive<i32>>>();
-            return if remainder.len() < 2 {
-                (range, remainder)
-            } else {
-                remainder.push(range);
-                join_range(&remainder)
-            };
-        } else {
-            indexes_to_remove.iter().rev().for_each(|i| {
-                indexes.remove(i);
-            });
+// The leftmost x in 0..=max that none of the ranges cover, if any
+fn first_gap(ranges: &[RangeInclusive<i32>], max: i32) -> Option<i32> {
+    let mut sorted = ranges.to_vec();
+    sorted.sort_by_key(|range| *range.start());
+    // Everything left of x is covered
+    let mut x = 0;
+    for range in sorted {
+        if *range.start() > x {
+            break;
         }
+        x = x.max(range.end().saturating_add(1));
+    }
+    (x <= max).then_some(x)
+}
+
+impl Default for SensorNetwork {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -131,28 +100,95 @@ impl SensorNetwork {
         result.len()
     }
 
-    pub fn get_frequency(&self, max: i32) -> i64 {
-        for row in 0..=max {
+    // Rows are checked across threads, and the first with a gap wins
+    // however they're scheduled. None if the area's all covered
+    pub fn get_frequency(&self, max: i32) -> Option<i64> {
+        (0..=max).into_par_iter().find_map_first(|row| {
             let ranges = self
                 .sensors
                 .iter()
                 .filter_map(|sensor| sensor.get_range_for_row(row))
                 .collect::<Vec<RangeInclusive<i32>>>();
-            let (range, remainder) = join_range(&ranges);
+            let x = first_gap(&ranges, max)?;
+            ranges.iter().for_each(|r| {
+                debug!(range = ?r, "covered");
+            });
+            debug!(x, row, "found the gap");
+            Some((i64::from(x) * 4_000_000) + i64::from(row))
+        })
+    }
+}
+
+impl SensorNetwork {
+    // The sample searches a much smaller area than the real input
+    fn is_sample(&self) -> bool {
+        self.bottom_right.x < 100 && self.bottom_right.y < 100
+    }
+}
+
+impl Solution for SensorNetwork {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut sensor_network = SensorNetwork::new();
+        let re = Regex::new(
+            r"^Sensor at x=(\-?\d+), y=(\-?\d+): closest beacon is at x=(\-?\d+), y=(\-?\d+)$",
+        )
+        .unwrap();
 
-            if !remainder.is_empty() {
-                ranges.iter().for_each(|r| {
-                    println!("- {:?}", r);
-                });
-                let x = if range.end() < remainder.get(0).unwrap().start() {
-                    range.end() + 1
+        parse_lines(input, |line| {
+            let caps = re.captures(line).ok_or_else(|| {
+                let expected = "Sensor at x=<n>, y=<n>: closest beacon is at x=<n>, y=<n>";
+                ParseError::at(line, line, expected)
+            })?;
+            let coord = |index: usize| number(line, caps.get(index).unwrap().as_str());
+            sensor_network.add_line(coord(1)?, coord(2)?, coord(3)?, coord(4)?);
+            Ok(())
+        })?;
+        Ok(sensor_network)
+    }
+    fn part1(&self) -> Answer {
+        let row = if self.is_sample() { 10 } else { 2_000_000 };
+        self.cannot_be(row).into()
+    }
+    fn part2(&self) -> Answer {
+        let max = if self.is_sample() { 20 } else { 4_000_000 };
+        self.get_frequency(max).map_or(Answer::None, Answer::from)
+    }
+}
+
+impl Generate for SensorNetwork {
+    // `size` sensors, at least four, around a distress beacon hidden
+    // somewhere in the search area. Every sensor's beacon is nearer than
+    // the distress beacon, and one at each corner of the area reaches all
+    // but it, so the beacon is the only place left
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const MAX: i64 = 4_000_000;
+        let distress = (rng.range(1..MAX), rng.range(1..MAX));
+        let corners = [(0, 0), (MAX, 0), (0, MAX), (MAX, MAX)];
+        let others: Vec<_> = (4..size)
+            .map(|_| (rng.range(0..MAX + 1), rng.range(0..MAX + 1)))
+            .filter(|&sensor| sensor != distress)
+            .collect();
+        corners
+            .into_iter()
+            .chain(others)
+            .enumerate()
+            .map(|(index, (x, y))| {
+                let reach = (x - distress.0).abs() + (y - distress.1).abs() - 1;
+                let reach = if index < 4 {
+                    reach
                 } else {
-                    range.start() - 1
-                } as i64;
-                println!("({}. {})", x, row);
-                return (x * 4_000_000) + row as i64;
-            }
-        }
-        0
+                    rng.range(0..reach + 1)
+                };
+                let across = rng.range(0..reach + 1);
+                let beacon = (
+                    x + across * *rng.pick(&[-1, 1]),
+                    y + (reach - across) * *rng.pick(&[-1, 1]),
+                );
+                format!(
+                    "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+                    x, y, beacon.0, beacon.1
+                )
+            })
+            .collect()
     }
 }
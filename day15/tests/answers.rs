@@ -0,0 +1,61 @@
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day15::SensorNetwork;
+
+#[test]
+fn sample() {
+    check::<SensorNetwork>("sample.txt", 26, 56_000_011);
+}
+
+#[test]
+fn input() {
+    check_input::<SensorNetwork>("input.txt", 5_809_294, 10_693_731_308_112_i64);
+}
+
+#[test]
+fn generated() {
+    // Every input searches the whole area, so one is slow enough
+    check_generated::<SensorNetwork>(10, 1);
+}
+
+#[test]
+fn rows_out_of_reach() {
+    // Most rows of the search area are out of this sensor's reach, so the
+    // first gap is in the top left corner
+    let network =
+        SensorNetwork::parse("Sensor at x=5, y=5: closest beacon is at x=5, y=6\n").unwrap();
+    assert_eq!(network.get_frequency(20), Some(0));
+    assert_eq!(network.part2(), Answer::Num(0));
+}
+
+#[test]
+fn rows_split_many_ways() {
+    // Row 0 is covered in three separate stretches, -1 to 1, 4 to 6 and 9
+    // to 11, so its first gap is at x=2
+    let network = SensorNetwork::parse(
+        "Sensor at x=0, y=0: closest beacon is at x=0, y=1\n\
+         Sensor at x=5, y=0: closest beacon is at x=5, y=1\n\
+         Sensor at x=10, y=0: closest beacon is at x=10, y=1\n",
+    )
+    .unwrap();
+    assert_eq!(network.get_frequency(20), Some(2 * 4_000_000));
+}
+
+#[test]
+fn gap_at_the_left_edge() {
+    // Rows 0 to 10 are covered across the area, and row 11 only from x=1
+    let network =
+        SensorNetwork::parse("Sensor at x=10, y=0: closest beacon is at x=10, y=-20\n").unwrap();
+    assert_eq!(network.get_frequency(20), Some(11));
+}
+
+#[test]
+fn gap_past_the_area() {
+    // Row 0 has a gap at x=21, just past the area, which doesn't count, so
+    // the first gap is at the right edge of row 1
+    let network = SensorNetwork::parse(
+        "Sensor at x=5, y=0: closest beacon is at x=5, y=15\n\
+         Sensor at x=26, y=0: closest beacon is at x=26, y=4\n",
+    )
+    .unwrap();
+    assert_eq!(network.get_frequency(20), Some(20 * 4_000_000 + 1));
+}
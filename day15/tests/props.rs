@@ -0,0 +1,43 @@
+use aoc_common::Solution;
+use day15::SensorNetwork;
+use proptest::prelude::*;
+
+type Pair = ((i32, i32), (i32, i32));
+
+// Sensors and their beacons on a grid small enough to check every position
+fn pairs() -> impl Strategy<Value = Vec<Pair>> {
+    let pt = || (-10..20_i32, -10..20_i32);
+    prop::collection::vec((pt(), pt()), 1..6)
+}
+
+fn distance((x1, y1): (i32, i32), (x2, y2): (i32, i32)) -> i32 {
+    (x1 - x2).abs() + (y1 - y2).abs()
+}
+
+// Positions in the row some sensor is closer to than its beacon, leaving out
+// the beacons themselves
+fn brute_force(pairs: &[Pair], row: i32) -> usize {
+    (-70..80)
+        .filter(|&x| {
+            let near = pairs
+                .iter()
+                .any(|&(sensor, beacon)| distance(sensor, (x, row)) <= distance(sensor, beacon));
+            let beacon = pairs.iter().any(|&(_, beacon)| beacon == (x, row));
+            near && !beacon
+        })
+        .count()
+}
+
+proptest! {
+    #[test]
+    fn row_coverage_matches_brute_force(pairs in pairs(), row in -15..25_i32) {
+        let input: String = pairs
+            .iter()
+            .map(|((sx, sy), (bx, by))| {
+                format!("Sensor at x={}, y={}: closest beacon is at x={}, y={}\n", sx, sy, bx, by)
+            })
+            .collect();
+        let network = SensorNetwork::parse(&input).unwrap();
+        prop_assert_eq!(network.cannot_be(row), brute_force(&pairs, row));
+    }
+}
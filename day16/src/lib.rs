@@ -1,274 +1,303 @@
-use itertools::Itertools;
+use aoc_common::{bfs_reach, Answer, Generate, ParseError, Rng, Solution};
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_while},
+    bytes::complete::{tag, take_while_m_n},
     character::complete::digit1,
     combinator::map_res,
     multi::separated_list0,
     sequence::tuple,
 };
-use std::cmp::Ordering;
-use std::collections::{BTreeSet, BinaryHeap, HashMap, HashSet, VecDeque};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
 
-#[derive(Debug)]
-struct Room<'a> {
-    flow_rate: u32,
-    connections: HashSet<&'a str>,
+// A valve's name of two to four letters, copied out of the input so the
+// cave doesn't borrow from it. Real inputs only use two, longer names make
+// room for the caves the generator makes up.
+#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+struct Valve([u8; 4]);
+
+impl Valve {
+    fn new(name: &str) -> Self {
+        let mut letters = [0; 4];
+        letters[..name.len()].copy_from_slice(name.as_bytes());
+        Self(letters)
+    }
 }
 
-#[derive(Debug)]
-pub struct Cave<'a> {
-    rooms: HashMap<&'a str, Room<'a>>,
+// Where both of us start
+const START: Valve = Valve(*b"AA\0\0");
+
+#[derive(Debug, Clone)]
+struct Room {
+    flow_rate: u32,
+    connections: HashSet<Valve>,
 }
 
-#[derive(PartialEq, Eq)]
-struct Node<'a> {
-    cost: u32,
-    curr: &'a str,
+#[derive(Debug, Clone)]
+pub struct Cave {
+    rooms: HashMap<Valve, Room>,
 }
 
-#[derive(Debug, Hash, PartialEq, Eq, Clone)]
-struct State<'a> {
-    opened: BTreeSet<&'a str>,
-    curr: &'a str,
-    elapsed: u32,
-    relieved: u32,
+// Only valves with some flow are worth opening, and a u64 marks which are
+const MAX_FLOWING: usize = 64;
+
+// The valves with some flow, numbered for a bitmask of which are open, and
+// the minutes it takes to walk between them. `walks[from][to]` is None where
+// there's no way there, and the start is the last `from`
+struct Flowing {
+    flow_rates: Vec<u64>,
+    walks: Vec<Vec<Option<u32>>>,
 }
 
-impl<'a> Ord for Node<'a> {
-    fn cmp(&self, other: &Self) -> Ordering {
-        other.cost.cmp(&self.cost)
+impl Flowing {
+    fn start(&self) -> usize {
+        self.flow_rates.len()
+    }
+
+    // Opens one valve after another from `at`, with `time_left` minutes to
+    // go, keeping the most pressure each set of open valves can release.
+    // A valve's pressure is counted in full as it's opened
+    fn open_valves(
+        &self,
+        at: usize,
+        open: u64,
+        time_left: u32,
+        released: u64,
+        best: &mut HashMap<u64, u64>,
+    ) {
+        let most = best.entry(open).or_default();
+        *most = (*most).max(released);
+        for (next, &flow_rate) in self.flow_rates.iter().enumerate() {
+            if open & (1 << next) != 0 {
+                continue;
+            }
+            let Some(walk) = self.walks[at][next] else {
+                continue;
+            };
+            // Opening it takes a minute, and there has to be time after
+            let Some(left) = time_left.checked_sub(walk + 1).filter(|&left| left > 0) else {
+                continue;
+            };
+            let released = released + flow_rate * u64::from(left);
+            self.open_valves(next, open | 1 << next, left, released, best);
+        }
     }
-}
 
-impl<'a> PartialOrd for Node<'a> {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    // The most pressure each set of valves can release opened in `minutes`
+    fn best_releases(&self, minutes: u32) -> HashMap<u64, u64> {
+        let mut best = HashMap::new();
+        self.open_valves(self.start(), 0, minutes, 0, &mut best);
+        best
     }
 }
 
 fn parse_valve_name(i: &str) -> nom::IResult<&str, &str> {
-    take_while(|c: char| c.is_alphabetic())(i)
+    take_while_m_n(2, 4, |c: char| c.is_ascii_uppercase())(i)
 }
 
-impl<'a> Room<'a> {
-    pub fn new(flow_rate: u32, connections: Vec<&'a str>) -> Self {
-        let set: HashSet<&str> = connections.iter().map(|str| *str).collect();
+// The valve a line is about, its flow rate and the names of the valves its
+// tunnels lead to, as they appear in the line
+fn parse_room(line: &str) -> Result<(Valve, u32, Vec<&str>), ParseError> {
+    let mut parse_valve = tuple((
+        tag("Valve "),
+        parse_valve_name,
+        tag(" has flow rate="),
+        map_res(digit1, u32::from_str),
+        alt((
+            tag("; tunnels lead to valves "),
+            tag("; tunnel leads to valve "),
+        )),
+        separated_list0(tag(", "), parse_valve_name),
+    ));
+    let expected = "Valve <name> has flow rate=<n>; tunnels lead to valves <names>";
+    let (_, room_name, _, flow_rate, _, connections) = match parse_valve(line) {
+        Ok(("", result)) => result,
+        Ok((rest, _)) => return Err(ParseError::at(line, rest, "end of line")),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+            return Err(ParseError::at(line, err.input, expected));
+        }
+        Err(nom::Err::Incomplete(_)) => return Err(ParseError::at_end(line, expected)),
+    };
+    Ok((Valve::new(room_name), flow_rate, connections))
+}
+
+impl Room {
+    fn new(flow_rate: u32, connections: &[&str]) -> Self {
         Self {
             flow_rate,
-            connections: set,
+            connections: connections.iter().map(|name| Valve::new(name)).collect(),
         }
     }
 }
 
-impl<'a> Cave<'a> {
+impl Default for Cave {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Cave {
     pub fn new() -> Self {
         Self {
             rooms: HashMap::new(),
         }
     }
-    pub fn add_room(&mut self, line: &'a str) {
-        let mut parse_valve = tuple((
-            tag("Valve "),
-            parse_valve_name,
-            tag(" has flow rate="),
-            map_res(digit1, u32::from_str),
-            alt((
-                tag("; tunnels lead to valves "),
-                tag("; tunnel leads to valve "),
-            )),
-            separated_list0(tag(", "), parse_valve_name),
-        ));
-        let result = parse_valve(line).unwrap();
-        let (_, room_name, _, flow_rate, _, connections) = result.1;
-
-        self.rooms
-            .insert(room_name, Room::new(flow_rate, connections));
+    pub fn add_room(&mut self, line: &str) -> Result<(), ParseError> {
+        let (name, flow_rate, connections) = parse_room(line)?;
+        self.rooms.insert(name, Room::new(flow_rate, &connections));
+        Ok(())
     }
 
-    fn min_cost(&self, from: &str, to: &str) -> u32 {
-        let mut pq = BinaryHeap::new();
-        let mut seen = HashSet::new();
-
-        pq.push(Node {
-            cost: 0,
-            curr: from,
-        });
-
-        while let Some(Node { cost, curr }) = pq.pop() {
-            if curr == to {
-                return cost;
-            }
-            for neighbor in self.rooms[curr].connections.iter() {
-                if seen.insert(neighbor) {
-                    pq.push(Node {
-                        cost: cost + 1,
-                        curr: neighbor,
-                    });
-                }
-            }
-        }
-        u32::MAX
-    }
-    fn min_distances(&self) -> HashMap<(&'a str, &'a str), u32> {
-        self.rooms
+    fn flowing(&self) -> Flowing {
+        let mut valves: Vec<Valve> = self
+            .rooms
             .iter()
             .filter(|(_, room)| room.flow_rate > 0)
             .map(|(&name, _)| name)
-            .tuple_combinations()
-            .fold(HashMap::new(), |mut acc, (name1, name2)| {
-                acc.entry(("AA", name1))
-                    .or_insert_with(|| self.min_cost("AA", name1));
-                acc.entry(("AA", name2))
-                    .or_insert_with(|| self.min_cost("AA", name2));
-
-                let dist = self.min_cost(name1, name2);
-                acc.insert((name1, name2), dist);
-                acc.insert((name2, name1), dist);
-                acc
-            })
-    }
-    fn wait_until_end(
-        &self,
-        max_time: u32,
-        elapsed: u32,
-        relieved: u32,
-        opened: &BTreeSet<&str>,
-    ) -> u32 {
-        let time_left = max_time - elapsed;
-        let relieved_per_min: u32 = opened.iter().map(|name| self.rooms[name].flow_rate).sum();
-        relieved + (relieved_per_min * time_left)
-    }
-
-    pub fn part_1(&mut self) -> u32 {
-        let dist_map = self.min_distances();
-        let flowing: HashSet<_> = self
-            .rooms
+            .collect();
+        valves.sort();
+        let walks = valves
             .iter()
-            .filter(|(_, valve)| valve.flow_rate > 0)
-            .map(|(&name, _)| name)
+            .chain([&START])
+            .map(|&from| {
+                let reached =
+                    bfs_reach([from], |curr| self.rooms[curr].connections.iter().copied());
+                valves
+                    .iter()
+                    .map(|to| reached.cost(to).map(|cost| cost as u32))
+                    .collect()
+            })
             .collect();
+        Flowing {
+            flow_rates: valves
+                .iter()
+                .map(|name| self.rooms[name].flow_rate.into())
+                .collect(),
+            walks,
+        }
+    }
 
-        let mut max_relieved = 0;
-        let mut q = VecDeque::new();
-        let mut seen = HashSet::new();
-
-        q.push_back(State {
-            curr: "AA",
-            opened: BTreeSet::new(),
-            elapsed: 0,
-            relieved: 0,
-        });
-        seen.insert((BTreeSet::new(), 0, 0));
-
-        while let Some(State {
-            opened,
-            curr,
-            elapsed,
-            relieved,
-        }) = q.pop_front()
-        {
-            if opened.len() == flowing.len() || elapsed >= 30 {
-                let relieved_at_end = self.wait_until_end(30, elapsed, relieved, &opened);
-                max_relieved = max_relieved.max(relieved_at_end);
-                continue;
+    pub fn part_1(&self) -> u64 {
+        let best = self.flowing().best_releases(30);
+        best.into_values().max().unwrap_or(0)
+    }
+    // We each open a different set of valves, so the best pair of sets that
+    // don't share one. Sorted by pressure, most first, pairs stop being
+    // worth trying once they can't beat the best so far
+    pub fn part_2(&self) -> u64 {
+        let mut best: Vec<(u64, u64)> = self.flowing().best_releases(26).into_iter().collect();
+        best.sort_unstable_by_key(|&(_, released)| Reverse(released));
+        let mut most = 0;
+        for (i, &(human, released)) in best.iter().enumerate() {
+            if released * 2 <= most {
+                break;
             }
-            let unopened = flowing.iter().filter(|name| !opened.contains(*name));
-
-            for dest in unopened {
-                let cost = dist_map[&(curr, *dest)] + 1;
-                let new_elapsed = elapsed + cost;
-
-                if new_elapsed >= 30 {
-                    let relieved_at_end = self.wait_until_end(30, elapsed, relieved, &opened);
-                    max_relieved = max_relieved.max(relieved_at_end);
-                    continue;
+            for &(elephant, also_released) in &best[i..] {
+                if released + also_released <= most {
+                    break;
                 }
-
-                let relieved_per_min: u32 =
-                    opened.iter().map(|name| &self.rooms[name].flow_rate).sum();
-                let new_relieved = relieved + (relieved_per_min * cost);
-                let mut new_opened = opened.clone();
-                new_opened.insert(dest);
-
-                if seen.insert((new_opened.clone(), new_elapsed, new_relieved)) {
-                    q.push_back(State {
-                        opened: new_opened,
-                        curr: dest,
-                        elapsed: new_elapsed,
-                        relieved: new_relieved,
-                    });
+                if human & elephant == 0 {
+                    most = released + also_released;
                 }
             }
         }
-        max_relieved
+        most
     }
-    pub fn part_2(&mut self) -> u32 {
-        let dist_map = self.min_distances();
-        let flowing: HashSet<_> = self
-            .rooms
-            .iter()
-            .filter(|(_, valve)| valve.flow_rate > 0)
-            .map(|(&name, _)| name)
-            .collect();
-
-        let mut max_relieved_states: HashMap<BTreeSet<&str>, u32> = HashMap::new();
-        let mut q = VecDeque::new();
-
-        q.push_back(State {
-            curr: "AA",
-            opened: BTreeSet::new(),
-            elapsed: 0,
-            relieved: 0,
-        });
+}
 
-        while let Some(State {
-            opened,
-            curr,
-            elapsed,
-            relieved,
-        }) = q.pop_front()
+impl Solution for Cave {
+    // Builds the cave, checking every tunnel leads to a valve that exists
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut cave = Cave::new();
+        let mut tunnels = Vec::new();
+        for line in input.lines() {
+            let (name, flow_rate, connections) =
+                parse_room(line).map_err(|err| err.within(input, line))?;
+            cave.rooms.insert(name, Room::new(flow_rate, &connections));
+            tunnels.extend(connections);
+        }
+        if let Some(name) = tunnels
+            .iter()
+            .find(|name| !cave.rooms.contains_key(&Valve::new(name)))
         {
-            let relieved_at_end = self.wait_until_end(26, elapsed, relieved, &opened);
-            max_relieved_states
-                .entry(opened.clone())
-                .and_modify(|val| *val = relieved_at_end.max(*val))
-                .or_insert(relieved_at_end);
-
-            if opened.len() == flowing.len() || elapsed >= 26 {
-                continue;
-            }
-            let unopened = flowing.iter().filter(|name| !opened.contains(*name));
-
-            for dest in unopened {
-                let cost = dist_map[&(curr, *dest)] + 1;
-                let new_elapsed = elapsed + cost;
-
-                if new_elapsed >= 26 {
-                    continue;
-                }
+            return Err(ParseError::at(input, name, "a valve in the cave"));
+        }
+        if !cave.rooms.contains_key(&START) {
+            return Err(ParseError::at_end(input, "a valve named AA"));
+        }
+        let flowing = cave.rooms.values().filter(|room| room.flow_rate > 0);
+        if flowing.count() > MAX_FLOWING {
+            let expected = format!("at most {} valves with any flow", MAX_FLOWING);
+            return Err(ParseError::at_end(input, expected));
+        }
+        Ok(cave)
+    }
+    fn part1(&self) -> Answer {
+        self.part_1().into()
+    }
+    fn part2(&self) -> Answer {
+        self.part_2().into()
+    }
+}
 
-                let relieved_per_min: u32 =
-                    opened.iter().map(|name| &self.rooms[name].flow_rate).sum();
-                let new_relieved = relieved + (relieved_per_min * cost);
-                let mut new_opened = opened.clone();
-                new_opened.insert(dest);
+// The nth valve's name, AA first
+fn valve_name(mut n: usize) -> String {
+    let mut name = Vec::new();
+    while n > 0 || name.len() < 2 {
+        name.push(b'A' + (n % 26) as u8);
+        n /= 26;
+    }
+    name.reverse();
+    String::from_utf8(name).unwrap()
+}
 
-                q.push_back(State {
-                    opened: new_opened,
-                    curr: dest,
-                    elapsed: new_elapsed,
-                    relieved: new_relieved,
-                });
+impl Generate for Cave {
+    // `size` valves, at least three, all joined up. A quarter of them have
+    // any flow but never more than fifteen, as in the real inputs, or
+    // there'd be too many orders to open them in
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let num_valves = size.max(3);
+        let mut tunnels: Vec<Vec<usize>> = vec![Vec::new(); num_valves];
+        let join = |tunnels: &mut [Vec<usize>], a: usize, b: usize| {
+            if a != b && !tunnels[a].contains(&b) {
+                tunnels[a].push(b);
+                tunnels[b].push(a);
             }
+        };
+        // Mostly long corridors, as in the real caves, with a few shortcuts
+        for valve in 1..num_valves {
+            let other = valve - 1 - rng.below(valve.min(3));
+            join(&mut tunnels, valve, other);
         }
-        max_relieved_states
-            .iter()
-            .tuple_combinations()
-            .filter(|(human, elephant)| human.0.is_disjoint(elephant.0))
-            .map(|(human, elephant)| human.1 + elephant.1)
-            .max()
-            .unwrap()
+        for _ in 0..num_valves / 8 {
+            let (a, b) = (rng.below(num_valves), rng.below(num_valves));
+            join(&mut tunnels, a, b);
+        }
+        let mut flowing: Vec<usize> = (1..num_valves).collect();
+        rng.shuffle(&mut flowing);
+        flowing.truncate((num_valves / 4).clamp(2, 15));
+        let mut lines: Vec<String> = (0..num_valves)
+            .map(|valve| {
+                let flow_rate = match flowing.contains(&valve) {
+                    true => rng.range(1..26),
+                    false => 0,
+                };
+                let names: Vec<String> = tunnels[valve].iter().map(|&n| valve_name(n)).collect();
+                let leads = match names.len() {
+                    1 => "tunnel leads to valve",
+                    _ => "tunnels lead to valves",
+                };
+                format!(
+                    "Valve {} has flow rate={}; {} {}\n",
+                    valve_name(valve),
+                    flow_rate,
+                    leads,
+                    names.join(", ")
+                )
+            })
+            .collect();
+        rng.shuffle(&mut lines);
+        lines.concat()
     }
 }
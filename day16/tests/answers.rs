@@ -0,0 +1,52 @@
+use std::fs;
+
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day16::Cave;
+
+#[test]
+fn sample() {
+    check::<Cave>("sample.txt", 1651, 1707);
+}
+
+#[test]
+fn input() {
+    check_input::<Cave>("input.txt", 1923, 2594);
+}
+
+// The cave owns its valve names, so it can outlive the input it came from
+#[test]
+fn outlives_its_input() {
+    let cave = {
+        let input = fs::read_to_string("sample.txt").unwrap();
+        Cave::parse(&input).unwrap()
+    };
+    assert_eq!(cave.part1(), Answer::from(1651));
+}
+
+#[test]
+fn generated() {
+    check_generated::<Cave>(16, 3);
+}
+
+#[test]
+fn small_caves() {
+    // One flowing valve, opened at minute 2 and 2 for the elephant's time
+    let one = "Valve AA has flow rate=0; tunnels lead to valves BB\n\
+               Valve BB has flow rate=10; tunnels lead to valves AA\n";
+    let cave = Cave::parse(one).unwrap();
+    assert_eq!(cave.part1(), Answer::from(28 * 10));
+    assert_eq!(cave.part2(), Answer::from(24 * 10));
+    // No flowing valves
+    let none = "Valve AA has flow rate=0; tunnels lead to valves BB\n\
+                Valve BB has flow rate=0; tunnels lead to valves AA\n";
+    let cave = Cave::parse(none).unwrap();
+    assert_eq!(cave.part1(), Answer::from(0));
+    assert_eq!(cave.part2(), Answer::from(0));
+    // CC can't be reached
+    let cut_off = "Valve AA has flow rate=0; tunnels lead to valves BB\n\
+                   Valve BB has flow rate=10; tunnels lead to valves AA\n\
+                   Valve CC has flow rate=20; tunnels lead to valves AA\n";
+    let cave = Cave::parse(cut_off).unwrap();
+    assert_eq!(cave.part1(), Answer::from(28 * 10));
+    assert_eq!(cave.part2(), Answer::from(24 * 10));
+}
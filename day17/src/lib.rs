@@ -1,182 +1,163 @@
+use aoc_common::{
+    Animate, Answer, Colour, Frame, Generate, Grid, ParseError, Point, Rng, Solution,
+};
 use std::collections::{hash_map::Entry, HashMap};
 
-#[derive(Debug)]
+const WIDTH: usize = 7;
+
+#[derive(Debug, Clone)]
 pub struct Cave {
-    lines: Vec<u8>,
+    // The rocks that have stopped, row 0 lying on the floor
+    tower: Grid<bool>,
     jets: Vec<char>,
-    width: usize,
 }
 #[derive(Debug, Copy, Clone)]
 enum Shape {
     Horiz,
     Cross,
-    LShape,
+    Corner,
     Vert,
     Square,
 }
+// A rock on its way down, by its bottom row and leftmost column
+#[derive(Debug, Copy, Clone)]
+struct Rock {
+    shape: Shape,
+    left: usize,
+    bottom: usize,
+}
+
 impl Shape {
-    pub fn get_max_height(&self) -> usize {
+    // The cells the shape takes up, across and up from its bottom left
+    fn cells(&self) -> &'static [(usize, usize)] {
         match self {
-            Shape::Horiz => 1,
-            Shape::Cross => 3,
-            Shape::LShape => 3,
-            Shape::Vert => 4,
-            Shape::Square => 2,
+            Shape::Horiz => &[(0, 0), (1, 0), (2, 0), (3, 0)],
+            Shape::Cross => &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+            Shape::Corner => &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+            Shape::Vert => &[(0, 0), (0, 1), (0, 2), (0, 3)],
+            Shape::Square => &[(0, 0), (1, 0), (0, 1), (1, 1)],
         }
     }
-    pub fn get_max_width(&self) -> usize {
+    fn height(&self) -> usize {
         match self {
-            Shape::Horiz => 4,
+            Shape::Horiz => 1,
             Shape::Cross => 3,
-            Shape::LShape => 3,
-            Shape::Vert => 1,
+            Shape::Corner => 3,
+            Shape::Vert => 4,
             Shape::Square => 2,
         }
     }
-    pub fn get_bits(&self) -> u16 {
-        match self {
-            Shape::Horiz => 0b1111_0000_0000_0000,
-            Shape::Cross => 0b0100_1110_0100_0000,
-            Shape::LShape => 0b0010_0010_1110_0000,
-            Shape::Vert => 0b1000_1000_1000_1000,
-            Shape::Square => 0b1100_1100_0000_0000,
-        }
+}
+
+impl Rock {
+    fn cells(&self) -> impl Iterator<Item = Point<usize>> + '_ {
+        self.shape
+            .cells()
+            .iter()
+            .map(|&(dx, dy)| Point::new(self.left + dx, self.bottom + dy))
     }
-    pub fn get_bits_row(&self, row: usize) -> u8 {
-        let place_bits = self.get_bits();
-        match row {
-            1 => ((place_bits & 0b1111_0000_0000_0000) >> 12)
-                .try_into()
-                .unwrap(),
-            2 => ((place_bits & 0b0000_1111_0000_0000) >> 8)
-                .try_into()
-                .unwrap(),
-            3 => ((place_bits & 0b0000_0000_1111_0000) >> 4)
-                .try_into()
-                .unwrap(),
-            4 => (place_bits & 0b0000_0000_0000_1111).try_into().unwrap(),
-            _ => panic!("Shape only has 4 rows"),
-        }
+    fn top(&self) -> usize {
+        self.bottom + self.shape.height() - 1
+    }
+    // Whether the rock takes up a cell of the cave
+    fn covers(&self, row: usize, col: usize) -> bool {
+        self.cells().any(|pt| pt == Point::new(col, row))
     }
 }
 
 impl Cave {
     pub fn new(str: String) -> Self {
         Self {
-            lines: Vec::new(),
+            tower: Grid::new(WIDTH, 0, false),
             jets: str.as_str().chars().collect(),
-            width: 7,
         }
     }
     fn get_shape(rock_num: usize) -> Shape {
         match rock_num % 5 {
             0 => Shape::Horiz,
             1 => Shape::Cross,
-            2 => Shape::LShape,
+            2 => Shape::Corner,
             3 => Shape::Vert,
             4 => Shape::Square,
             _ => panic!("Shouldn't get here"),
         }
     }
-    fn get_shifted_line(&self, row: usize, left: usize) -> u16 {
-        if row >= self.lines.len() {
-            0x00
-        } else {
-            let result: u8 = match left {
-                0 => (self.lines[row] & 0b0111_1000) >> 3,
-                1 => (self.lines[row] & 0b0011_1100) >> 2,
-                2 => (self.lines[row] & 0b0001_1110) >> 1,
-                3 => self.lines[row] & 0b0000_1111,
-                4 => (self.lines[row] & 0b0000_0111) << 1,
-                5 => (self.lines[row] & 0b0000_0011) << 2,
-                6 => (self.lines[row] & 0b0000_0001) << 3,
-                _ => panic!("Unknown left"),
-            };
-            (result & 0x0f) as u16
-        }
-    }
 
-    fn get_u16(&self, top: usize, left: usize) -> u16 {
-        let mut row: Vec<u16> = vec![0; 4];
+    // Whether the rock is between the walls and clear of the tower. Above
+    // the tower is all air.
+    fn fits(&self, rock: Rock) -> bool {
+        rock.cells()
+            .all(|pt| pt.x < WIDTH && !self.tower.get(pt).copied().unwrap_or(false))
+    }
 
-        row[0] = self.get_shifted_line(top, left);
-        if top > 0 {
-            row[1] = self.get_shifted_line(top - 1, left);
-        }
-        if top > 1 {
-            row[2] = self.get_shifted_line(top - 2, left);
+    fn place_rock(&mut self, rock: Rock) {
+        while self.tower.height() <= rock.top() {
+            self.tower.push_row([false; WIDTH]);
         }
-        if top > 2 {
-            row[3] = self.get_shifted_line(top - 3, left);
+        for pt in rock.cells() {
+            self.tower[pt] = true;
         }
-        return (row[0] << 12) | (row[1] << 8) | (row[2] << 4) | (row[3]);
     }
 
-    fn can_move(&self, shape: Shape, new_rock_top: i32, new_rock_left: i32) -> bool {
-        if (new_rock_left < 0)
-            || (new_rock_top < 0)
-            || (new_rock_left + shape.get_max_width() as i32 > self.width as i32)
-        {
-            false
-        } else {
-            (shape.get_bits() & self.get_u16(new_rock_top as usize, new_rock_left as usize)) == 0
+    // Each rock appears two in from the left wall with three empty rows
+    // below it
+    fn new_rock(&self, shape: Shape) -> Rock {
+        Rock {
+            shape,
+            left: 2,
+            bottom: self.tower.height() + 3,
         }
     }
 
-    fn place_line(&mut self, row: u32, bits: u8, rock_left: u32) {
-        let row_bits = match rock_left {
-            6 => bits >> 3,
-            5 => bits >> 2,
-            4 => bits >> 1,
-            3 => bits,
-            2 => bits << 1,
-            1 => bits << 2,
-            0 => bits << 3,
-            _ => panic!("Huh??"),
+    // Pushes the rock with the next jet of gas, then lets it fall a row.
+    // Once it can't fall any further it's placed and this returns false.
+    fn fall(&mut self, rock: &mut Rock, jet_space: &mut usize) -> bool {
+        let pushed = match self.jets[*jet_space] {
+            '<' => rock.left.checked_sub(1),
+            '>' => Some(rock.left + 1),
+            _ => panic!("Unexpected jet"),
         };
-        self.lines[row as usize] |= row_bits;
-    }
-
-    fn place_rock(&mut self, shape: Shape, rock_left: u32, rock_top: u32) {
-        //println!("Placing {:?} {} {}", shape, rock_top, rock_left);
-        // OR in with field, add new lines if necessary
-        while rock_top >= self.lines.len() as u32 {
-            self.lines.push(0b00);
-        }
-        match shape.get_max_height() {
-            4 => {
-                self.place_line(rock_top - 3, shape.get_bits_row(4), rock_left);
-                self.place_line(rock_top - 2, shape.get_bits_row(3), rock_left);
-                self.place_line(rock_top - 1, shape.get_bits_row(2), rock_left);
-                self.place_line(rock_top, shape.get_bits_row(1), rock_left);
-            }
-            3 => {
-                self.place_line(rock_top - 2, shape.get_bits_row(3), rock_left);
-                self.place_line(rock_top - 1, shape.get_bits_row(2), rock_left);
-                self.place_line(rock_top, shape.get_bits_row(1), rock_left);
+        if let Some(left) = pushed {
+            let moved = Rock { left, ..*rock };
+            if self.fits(moved) {
+                *rock = moved;
             }
-            2 => {
-                self.place_line(rock_top - 1, shape.get_bits_row(2), rock_left);
-                self.place_line(rock_top, shape.get_bits_row(1), rock_left);
+        }
+        *jet_space = (*jet_space + 1) % self.jets.len();
+        let fallen = rock
+            .bottom
+            .checked_sub(1)
+            .map(|bottom| Rock { bottom, ..*rock })
+            .filter(|&fallen| self.fits(fallen));
+        match fallen {
+            Some(fallen) => {
+                *rock = fallen;
+                true
             }
-            1 => {
-                self.place_line(rock_top, shape.get_bits_row(1), rock_left);
+            None => {
+                self.place_rock(*rock);
+                false
             }
-            _ => panic!("Shouldn't get here!"),
         }
     }
 
+    // The top 8 rows of the tower, a byte each
     fn get_skyline(&self) -> u64 {
         let mut last_lines = [0_u8; 8];
-        let len = self.lines.len();
+        let height = self.tower.height();
 
-        for i in 0..8 {
-            last_lines[i] = self.lines[len - (1 + i)];
+        for (i, line) in last_lines.iter_mut().enumerate() {
+            *line = self
+                .tower
+                .row(height - (1 + i))
+                .iter()
+                .fold(0, |bits, &rock| (bits << 1) | u8::from(rock));
         }
         u64::from_ne_bytes(last_lines)
     }
 
-    pub fn part_1(&mut self, num_rocks: usize) -> usize {
+    // Height of the tower once `num_rocks` rocks have stopped
+    fn tower_height(&mut self, num_rocks: usize) -> usize {
         let mut jet_space: usize = 0;
         let mut shape_num: usize = 0;
         let mut rock_num: usize = 0;
@@ -184,47 +165,11 @@ impl Cave {
         let mut cycle_height = 0;
 
         while rock_num < num_rocks {
-            let shape = Self::get_shape(shape_num);
+            let mut rock = self.new_rock(Self::get_shape(shape_num));
             shape_num = (shape_num + 1) % 5;
-            let mut rock_left = 2;
-            let mut rock_top = self.lines.len() + 2 + shape.get_max_height();
-
-            loop {
-                /*
-                                println!(
-                                    "New rock of type {:?} at {} {} - {} {}",
-                                    shape, rock_top, rock_left, self.jets[jet_space as usize], jet_space
-                                );
-                */
-                match self.jets[jet_space as usize] {
-                    '<' => {
-                        rock_left -= if self.can_move(shape, rock_top as i32, rock_left as i32 - 1)
-                        {
-                            1
-                        } else {
-                            0
-                        }
-                    }
-                    '>' => {
-                        rock_left += if self.can_move(shape, rock_top as i32, rock_left as i32 + 1)
-                        {
-                            1
-                        } else {
-                            0
-                        }
-                    }
-                    _ => panic!("Unexpected jet"),
-                }
-                jet_space = (jet_space + 1) % self.jets.len();
-                if self.can_move(shape, rock_top as i32 - 1, rock_left as i32) {
-                    rock_top -= 1;
-                } else {
-                    break;
-                }
-            }
-            self.place_rock(shape, rock_left as u32, rock_top as u32);
+            while self.fall(&mut rock, &mut jet_space) {}
             rock_num += 1;
-            if self.lines.len() < 8 {
+            if self.tower.height() < 8 {
                 continue;
             }
             // If we have seen same shape_num, same jet_index, and same top of cave
@@ -235,21 +180,95 @@ impl Cave {
                     let num_rocks_in_cycle = rock_num - old_num;
                     let num_cycles = (num_rocks - rock_num) / num_rocks_in_cycle;
                     rock_num += num_rocks_in_cycle * num_cycles;
-                    cycle_height += num_cycles * (self.lines.len() - old_height);
+                    cycle_height += num_cycles * (self.tower.height() - old_height);
                     seen.clear();
                 }
                 Entry::Vacant(e) => {
-                    e.insert((rock_num, self.lines.len()));
+                    e.insert((rock_num, self.tower.height()));
                 }
             }
-            //self.display();
         }
-        self.lines.len() + cycle_height
+        self.tower.height() + cycle_height
     }
-    fn display(&self) {
-        for line in self.lines.iter().rev() {
-            println!("## {:07b}", line);
+
+    // The tower and its floor, with the falling rock
+    fn frame(&self, rock: &Rock, caption: impl Into<String>) -> Frame {
+        let top = self.tower.height().max(rock.top() + 1);
+        let mut frame = Frame::new(caption);
+        for row in (0..top).rev() {
+            let mut cells = vec![('|', Colour::Plain)];
+            cells.extend((0..WIDTH).map(|col| {
+                if rock.covers(row, col) {
+                    ('@', Colour::Yellow)
+                } else if self.tower.get(Point::new(col, row)) == Some(&true) {
+                    ('#', Colour::Grey)
+                } else {
+                    ('.', Colour::Grey)
+                }
+            }));
+            cells.push(('|', Colour::Plain));
+            frame.push_row(cells);
         }
-        println!("## -------");
+        let mut floor = vec![('+', Colour::Plain)];
+        floor.extend(std::iter::repeat_n(('-', Colour::Plain), WIDTH));
+        floor.push(('+', Colour::Plain));
+        frame.push_row(floor);
+        frame.with_focus(Point::new(rock.left + 1, top - 1 - rock.top()))
+    }
+}
+
+impl Animate for Cave {
+    // A frame for each row a rock falls. Part 2's trillion rocks are far too
+    // many to watch, so it just keeps dropping them.
+    fn frames(&self, part: u8) -> Box<dyn Iterator<Item = Frame> + '_> {
+        let limit = if part == 1 { 2022 } else { usize::MAX };
+        let mut cave = self.clone();
+        let mut jet_space = 0;
+        let mut rocks = 0;
+        let mut rock = cave.new_rock(Self::get_shape(0));
+        Box::new(std::iter::from_fn(move || {
+            if rocks == limit {
+                return None;
+            }
+            let caption = format!(
+                "Day 17 part {}: {} rocks, the tower is {} high",
+                part,
+                rocks,
+                cave.tower.height()
+            );
+            let frame = cave.frame(&rock, caption);
+            if !cave.fall(&mut rock, &mut jet_space) {
+                rocks += 1;
+                rock = cave.new_rock(Self::get_shape(rocks));
+            }
+            Some(frame)
+        }))
+    }
+}
+
+impl Solution for Cave {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let jets = input.trim();
+        if let Some(pos) = jets.find(|ch| ch != '<' && ch != '>') {
+            return Err(ParseError::at(input, &jets[pos..], "a jet of gas, < or >"));
+        }
+        if jets.is_empty() {
+            return Err(ParseError::at_end(input, "a jet of gas, < or >"));
+        }
+        Ok(Cave::new(jets.to_string()))
+    }
+    fn part1(&self) -> Answer {
+        self.clone().tower_height(2022).into()
+    }
+    fn part2(&self) -> Answer {
+        self.clone().tower_height(1_000_000_000_000).into()
+    }
+}
+
+impl Generate for Cave {
+    // A jet pattern `size` pushes long
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let jets: String = (0..size.max(1)).map(|_| *rng.pick(&['<', '>'])).collect();
+        jets + "\n"
     }
 }
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day17::Cave;
+
+#[test]
+fn sample() {
+    check::<Cave>("sample.txt", 3068, 1_514_285_714_288_i64);
+}
+
+#[test]
+fn input() {
+    check_input::<Cave>("input.txt", 3119, 1_536_994_219_669_i64);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Cave>(50, 3);
+}
@@ -0,0 +1,10 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day18::Droplet;
+
+fn solve(c: &mut Criterion) {
+    bench::<Droplet>(c, "day18", &["sample.txt", "shortest.txt", "input.txt"]);
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
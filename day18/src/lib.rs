@@ -1,3 +1,6 @@
+use aoc_common::{
+    bfs_reach, parse_lines, Answer, Generate, ParseError, Point3, Reached, Rng, Solution,
+};
 use nom::{
     bytes::complete::tag, character::complete::digit1, combinator::map_res, multi::separated_list1,
     IResult,
@@ -5,51 +8,26 @@ use nom::{
 use std::collections::HashSet;
 use std::str::FromStr;
 
-#[derive(Debug, Copy, Clone, Eq, Hash, PartialEq)]
-pub struct Cube {
-    x: i32,
-    y: i32,
-    z: i32,
-}
+pub type Cube = Point3<i32>;
 
 #[derive(Debug)]
 pub struct Droplet {
     cubes: HashSet<Cube>,
 }
 
-enum Dimension {
-    X,
-    Y,
-    Z,
+// Within the droplet's bounds, with a layer of air all round
+fn in_bounds(cube: &Cube, min: Cube, max: Cube) -> bool {
+    cube.x >= min.x - 1
+        && cube.x <= max.x + 1
+        && cube.y >= min.y - 1
+        && cube.y <= max.y + 1
+        && cube.z >= min.z - 1
+        && cube.z <= max.z + 1
 }
 
-impl Cube {
-    pub fn new(x: i32, y: i32, z: i32) -> Self {
-        Self { x, y, z }
-    }
-    fn neighbors(&self) -> Vec<Cube> {
-        let mut neighbors = Vec::new();
-
-        for dimension in [Dimension::X, Dimension::Y, Dimension::Z] {
-            for offset in [-1, 1] {
-                let mut neighbor = self.clone();
-                match dimension {
-                    Dimension::X => neighbor.x += offset,
-                    Dimension::Y => neighbor.y += offset,
-                    Dimension::Z => neighbor.z += offset,
-                }
-                neighbors.push(neighbor);
-            }
-        }
-        neighbors
-    }
-    fn in_bounds(&self, min: Self, max: Self) -> bool {
-        self.x >= min.x - 1
-            && self.x <= max.x + 1
-            && self.y >= min.y - 1
-            && self.y <= max.y + 1
-            && self.z >= min.z - 1
-            && self.z <= max.z + 1
+impl Default for Droplet {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -59,29 +37,22 @@ impl Droplet {
             cubes: HashSet::new(),
         }
     }
-    pub fn add_line(&mut self, line: &String) {
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
         let result: IResult<&str, Vec<i32>> =
             separated_list1(tag(","), map_res(digit1, i32::from_str))(line);
-        let numbers = result.unwrap().1;
+        let numbers = match result {
+            Ok(("", numbers)) if numbers.len() == 3 => numbers,
+            Ok(("", _)) => return Err(ParseError::at(line, line, "a cube like 2,2,2")),
+            Ok((rest, _)) => return Err(ParseError::at(line, rest, "\",\" or end of line")),
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                return Err(ParseError::at(line, err.input, "a number"));
+            }
+            Err(nom::Err::Incomplete(_)) => return Err(ParseError::at_end(line, "a number")),
+        };
         self.cubes
             .insert(Cube::new(numbers[0], numbers[1], numbers[2]));
+        Ok(())
     }
-    pub fn pt_1(&self) -> usize {
-        self.cubes
-            .iter()
-            .flat_map(|cube| cube.neighbors())
-            .filter(|cube| !self.cubes.contains(cube))
-            .count()
-    }
-    pub fn pt_2(&self) -> usize {
-        let exposed = self.exposed();
-        self.cubes
-            .iter()
-            .flat_map(|cube| cube.neighbors())
-            .filter(|cube| exposed.contains(cube))
-            .count()
-    }
-
     fn bounds(&self) -> (Cube, Cube) {
         let mut min = Cube::new(i32::MAX, i32::MAX, i32::MAX);
         let mut max = Cube::new(i32::MIN, i32::MIN, i32::MIN);
@@ -95,28 +66,60 @@ impl Droplet {
         }
         (min, max)
     }
-    fn exposed(&self) -> HashSet<Cube> {
+    // The air outside the droplet, flooded from a corner of the bounds
+    fn exposed(&self) -> Reached<Cube, usize> {
         let (min, max) = self.bounds();
-        let mut exposed = HashSet::new();
-
-        let start = Cube::new(0, 0, 0);
-        let mut stack = Vec::new();
-        let mut seen = HashSet::new();
+        let start = min - Cube::new(1, 1, 1);
+        bfs_reach([start], |cube| {
+            cube.neighbours6()
+                .into_iter()
+                .filter(|neighbor| !self.cubes.contains(neighbor) && in_bounds(neighbor, min, max))
+        })
+    }
+}
 
-        stack.push(start);
-        seen.insert(start);
+impl Solution for Droplet {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut droplet = Droplet::new();
+        parse_lines(input, |line| droplet.add_line(line))?;
+        Ok(droplet)
+    }
+    fn part1(&self) -> Answer {
+        self.cubes
+            .iter()
+            .flat_map(|cube| cube.neighbours6())
+            .filter(|cube| !self.cubes.contains(cube))
+            .count()
+            .into()
+    }
+    fn part2(&self) -> Answer {
+        let exposed = self.exposed();
+        self.cubes
+            .iter()
+            .flat_map(|cube| cube.neighbours6())
+            .filter(|cube| exposed.contains(cube))
+            .count()
+            .into()
+    }
+}
 
-        while let Some(cube) = stack.pop() {
-            for neighbor in cube.neighbors() {
-                if self.cubes.contains(&neighbor) || !neighbor.in_bounds(min, max) {
-                    continue;
-                }
-                if seen.insert(neighbor) {
-                    stack.push(neighbor);
-                    exposed.insert(neighbor);
-                }
+impl Generate for Droplet {
+    // A lumpy droplet of `size` cubes grown a cube at a time from the
+    // middle, leaving pockets of air inside as it goes
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let middle = (size as f64).cbrt() as i32 + 2;
+        let mut cubes = vec![Cube::new(middle, middle, middle)];
+        let mut seen: HashSet<Cube> = cubes.iter().copied().collect();
+        while cubes.len() < size.max(1) {
+            let from = cubes[rng.below(cubes.len())];
+            let cube = *rng.pick(&from.neighbours6());
+            if cube.x >= 0 && cube.y >= 0 && cube.z >= 0 && seen.insert(cube) {
+                cubes.push(cube);
             }
         }
-        exposed
+        cubes
+            .iter()
+            .map(|cube| format!("{},{},{}\n", cube.x, cube.y, cube.z))
+            .collect()
     }
 }
@@ -0,0 +1,22 @@
+use aoc_common::{check, check_generated, check_input};
+use day18::Droplet;
+
+#[test]
+fn sample() {
+    check::<Droplet>("sample.txt", 64, 58);
+}
+
+#[test]
+fn shortest() {
+    check::<Droplet>("shortest.txt", 10, 10);
+}
+
+#[test]
+fn input() {
+    check_input::<Droplet>("input.txt", 3550, 2028);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Droplet>(200, 3);
+}
@@ -0,0 +1,16 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day19::Factory;
+
+fn solve(c: &mut Criterion) {
+    bench::<Factory>(c, "day19", &["sample.txt", "input.txt"]);
+}
+
+// Searching every blueprint of the real input takes most of a second, so
+// take fewer samples
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solve
+}
+criterion_main!(benches);
@@ -1,9 +1,11 @@
+use aoc_common::{bfs_reach, parse_lines, Answer, Generate, ParseError, Rng, Solution};
 use nom::{
     bytes::complete::take_till, character::complete::digit1, combinator::map_res, sequence::tuple,
     IResult,
 };
-use std::collections::VecDeque;
+use rayon::prelude::*;
 use std::str::FromStr;
+use tracing::{debug, debug_span, Span};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Material {
@@ -127,26 +129,9 @@ impl RobotCost {
 }
 
 impl Blueprint {
-    fn new(line: &str) -> Self {
-        let result: IResult<
-            &str,
-            (
-                &str,
-                i32,
-                &str,
-                i32,
-                &str,
-                i32,
-                &str,
-                i32,
-                &str,
-                i32,
-                &str,
-                i32,
-                &str,
-                i32,
-            ),
-        > = tuple((
+    // Only the numbers matter, the text between them is skipped
+    fn new(line: &str) -> Result<Self, ParseError> {
+        let result: IResult<&str, _> = tuple((
             take_till(|c: char| c.is_numeric()),
             map_res(digit1, i32::from_str),
             take_till(|c: char| c.is_numeric()),
@@ -162,14 +147,20 @@ impl Blueprint {
             take_till(|c: char| c.is_numeric()),
             map_res(digit1, i32::from_str),
         ))(line);
-        let tuple = result.unwrap().1;
-        Self {
+        let tuple = match result {
+            Ok((_, tuple)) => tuple,
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                return Err(ParseError::at(line, err.input, "a number"));
+            }
+            Err(nom::Err::Incomplete(_)) => return Err(ParseError::at_end(line, "a number")),
+        };
+        Ok(Self {
             num: tuple.1,
             ore_robot_cost: RobotCost::new(tuple.3, 0, 0),
             clay_robot_cost: RobotCost::new(tuple.5, 0, 0),
             obsidian_robot_cost: RobotCost::new(tuple.7, tuple.9, 0),
             geode_robot_cost: RobotCost::new(tuple.11, 0, tuple.13),
-        }
+        })
     }
     fn cost(&self, robot: Material) -> RobotCost {
         match robot {
@@ -218,60 +209,103 @@ impl Blueprint {
     }
 }
 
+impl Default for Factory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Factory {
     pub fn new() -> Self {
         Self {
             blueprints: Vec::new(),
         }
     }
-    pub fn add_line(&mut self, line: &str) {
-        self.blueprints.push(Blueprint::new(line));
-    }
-    pub fn pt_1(&self) -> i32 {
-        let mut quality_levels = 0;
-        for blueprint in self.blueprints.iter() {
-            println!("{:?}", blueprint);
-            let max = Self::search(&blueprint, 24);
-            println!("--{}", max);
-            quality_levels += max * blueprint.num;
-        }
-        quality_levels
-    }
-    pub fn pt_2(&self) -> i32 {
-        self.blueprints
-            .iter()
-            .take(3)
-            .map(|blueprint| Self::search(blueprint, 32))
-            .product()
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
+        self.blueprints.push(Blueprint::new(line)?);
+        Ok(())
     }
     fn search(factory: &Blueprint, minutes: usize) -> i32 {
-        let mut queue = VecDeque::new();
-        queue.push_back((Inventory::new(), 0, false));
-
         let mut cache = vec![0; minutes + 1];
 
-        while let Some((inv, min, built)) = queue.pop_front() {
-            let prior_best = cache[min];
-            if inv.geodes < prior_best {
-                continue;
-            }
-            cache[min] = prior_best.max(inv.geodes);
-
+        let reached = bfs_reach([(Inventory::new(), 0, false)], |&(inv, min, built)| {
+            let mut next = Vec::new();
             if min == minutes {
-                continue;
+                return next;
             }
             if factory.can_build(&inv, Material::Geode) {
-                queue.push_back((inv.build(factory, Material::Geode), min + 1, true));
-                continue;
-            }
-            queue.push_back((inv.next_after(), min + 1, false));
+                next.push((inv.build(factory, Material::Geode), min + 1, true));
+            } else {
+                next.push((inv.next_after(), min + 1, false));
 
-            for robot in [Material::Obsidian, Material::Clay, Material::Ore] {
-                if factory.can_build(&inv, robot) && factory.should_build(&inv, robot, built) {
-                    queue.push_back((inv.build(factory, robot), min + 1, true));
+                for robot in [Material::Obsidian, Material::Clay, Material::Ore] {
+                    if factory.can_build(&inv, robot) && factory.should_build(&inv, robot, built) {
+                        next.push((inv.build(factory, robot), min + 1, true));
+                    }
                 }
             }
-        }
-        return cache[minutes];
+            // Drop anything behind the best queued before it for the same
+            // minute, it won't catch up
+            let best = &mut cache[min + 1];
+            next.retain(|(inv, _, _)| {
+                *best = (*best).max(inv.geodes);
+                inv.geodes == *best
+            });
+            next
+        });
+        debug!(states = reached.len(), "searched");
+        cache[minutes]
+    }
+}
+
+impl Solution for Factory {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut factory = Factory::new();
+        parse_lines(input, |line| factory.add_line(line))?;
+        Ok(factory)
+    }
+    // Each blueprint is searched on its own, so they're shared out across
+    // threads
+    fn part1(&self) -> Answer {
+        let parent = Span::current();
+        self.blueprints
+            .par_iter()
+            .map(|blueprint| {
+                let _span =
+                    debug_span!(parent: &parent, "blueprint", num = blueprint.num).entered();
+                let max = Self::search(blueprint, 24);
+                debug!(geodes = max, "searched");
+                max * blueprint.num
+            })
+            .sum::<i32>()
+            .into()
+    }
+    fn part2(&self) -> Answer {
+        self.blueprints
+            .par_iter()
+            .take(3)
+            .map(|blueprint| Self::search(blueprint, 32))
+            .product::<i32>()
+            .into()
+    }
+}
+
+impl Generate for Factory {
+    // `size` blueprints with costs like the real ones
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (1..=size.max(1))
+            .map(|id| {
+                format!(
+                    "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. Each obsidian robot costs {} ore and {} clay. Each geode robot costs {} ore and {} obsidian.\n",
+                    id,
+                    rng.range(2..5),
+                    rng.range(2..5),
+                    rng.range(2..5),
+                    rng.range(5..21),
+                    rng.range(2..5),
+                    rng.range(5..21)
+                )
+            })
+            .collect()
     }
 }
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day19::Factory;
+
+#[test]
+fn sample() {
+    check::<Factory>("sample.txt", 33, 3472);
+}
+
+#[test]
+fn input() {
+    check_input::<Factory>("input.txt", 960, 2040);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Factory>(1, 3);
+}
@@ -0,0 +1,259 @@
+use aoc_common::{number, parse_lines, ParseError};
+
+/// The classic game, scored as the puzzle scores it.
+pub const CLASSIC: &str = include_str!("../games/rps.txt");
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    const NAMES: [(&'static str, Outcome); 3] = [
+        ("lose", Outcome::Lose),
+        ("draw", Outcome::Draw),
+        ("win", Outcome::Win),
+    ];
+}
+
+/// A move with the letters the guide writes it as in each column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Move {
+    pub name: String,
+    pub theirs: String,
+    pub ours: String,
+    pub score: i64,
+}
+
+/// How the guide's second column is read: as the move to play or as the
+/// outcome to reach.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Reading {
+    Shape,
+    Outcome,
+}
+
+impl Reading {
+    /// The readings by the same names as the classic strategies.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "shape" => Some(Reading::Shape),
+            "outcome" => Some(Reading::Outcome),
+            _ => None,
+        }
+    }
+}
+
+/// A game of an odd number of moves going round in a circle, each beating
+/// the (n - 1) / 2 after it and losing to the rest, so every pair of moves
+/// has a winner. Rock, paper, scissors is the game of 3.
+///
+/// Games are set up from a config file, one line for each move in order
+/// (`move <name> <their letter> <our letter> <score>`) and one for each
+/// outcome (`lose|draw|win <our letter> <score>`), with `#` for comments.
+#[derive(Debug, Clone)]
+pub struct Game {
+    moves: Vec<Move>,
+    // The letter and score of each outcome, indexed by `Outcome`
+    outcomes: [(String, i64); 3],
+}
+
+// One line of a config file
+enum Line {
+    Move(Move),
+    Outcome(Outcome, String, i64),
+    Blank,
+}
+
+fn parse_line(line: &str) -> Result<Line, ParseError> {
+    let content = line.split('#').next().unwrap_or_default();
+    let mut tokens = content.split_whitespace();
+    let Some(kind) = tokens.next() else {
+        return Ok(Line::Blank);
+    };
+    let mut next = |expected: &str| {
+        tokens
+            .next()
+            .ok_or_else(|| ParseError::at_end(content, expected))
+    };
+    let parsed = if kind == "move" {
+        let name = next("a move name")?.to_string();
+        let theirs = next("their letter for the move")?.to_string();
+        let ours = next("our letter for the move")?.to_string();
+        let score = number(line, next("the move's score")?)?;
+        Line::Move(Move {
+            name,
+            theirs,
+            ours,
+            score,
+        })
+    } else if let Some(&(_, outcome)) = Outcome::NAMES.iter().find(|(name, _)| *name == kind) {
+        let letter = next("our letter for the outcome")?.to_string();
+        let score = number(line, next("the outcome's score")?)?;
+        Line::Outcome(outcome, letter, score)
+    } else {
+        return Err(ParseError::at(line, kind, "move, lose, draw or win"));
+    };
+    if let Some(token) = tokens.next() {
+        return Err(ParseError::at(line, token, "end of line"));
+    }
+    Ok(parsed)
+}
+
+impl Game {
+    pub fn parse(config: &str) -> Result<Self, ParseError> {
+        let mut moves: Vec<Move> = Vec::new();
+        let mut outcomes: [Option<(String, i64)>; 3] = Default::default();
+        for (parsed, line) in parse_lines(config, parse_line)?
+            .into_iter()
+            .zip(config.lines())
+        {
+            let repeated = |what: &str| {
+                ParseError::at(line, line.trim_start(), format!("no second {}", what))
+                    .within(config, line)
+            };
+            match parsed {
+                Line::Move(m) => {
+                    if moves.iter().any(|other| other.name == m.name) {
+                        return Err(repeated(&format!("move called {}", m.name)));
+                    }
+                    if moves.iter().any(|other| other.theirs == m.theirs) {
+                        return Err(repeated(&format!("move they write as {}", m.theirs)));
+                    }
+                    if moves.iter().any(|other| other.ours == m.ours) {
+                        return Err(repeated(&format!("move we write as {}", m.ours)));
+                    }
+                    moves.push(m);
+                }
+                Line::Outcome(outcome, letter, score) => {
+                    if outcomes[outcome as usize].is_some() {
+                        return Err(repeated(Outcome::NAMES[outcome as usize].0));
+                    }
+                    if outcomes.iter().flatten().any(|(other, _)| *other == letter) {
+                        return Err(repeated(&format!("outcome written as {}", letter)));
+                    }
+                    outcomes[outcome as usize] = Some((letter, score));
+                }
+                Line::Blank => {}
+            }
+        }
+        if moves.len() < 3 || moves.len().is_multiple_of(2) {
+            return Err(ParseError::at_end(
+                config,
+                format!("an odd number of moves, at least 3, not {}", moves.len()),
+            ));
+        }
+        let [lose, draw, win] = outcomes;
+        let outcome = |found: Option<(String, i64)>, name| {
+            found.ok_or_else(|| ParseError::at_end(config, format!("a {} line", name)))
+        };
+        Ok(Self {
+            moves,
+            outcomes: [
+                outcome(lose, "lose")?,
+                outcome(draw, "draw")?,
+                outcome(win, "win")?,
+            ],
+        })
+    }
+
+    /// Rock, paper, scissors as the puzzle plays it.
+    pub fn classic() -> Self {
+        Self::parse(CLASSIC).expect("the classic game is valid")
+    }
+
+    /// The moves in order round the circle.
+    pub fn moves(&self) -> &[Move] {
+        &self.moves
+    }
+
+    /// Where the move called `name` is in the circle.
+    pub fn index(&self, name: &str) -> Option<usize> {
+        self.moves.iter().position(|m| m.name == name)
+    }
+
+    /// How our move fares against theirs, both given by index.
+    pub fn outcome(&self, ours: usize, theirs: usize) -> Outcome {
+        let n = self.moves.len();
+        match (theirs + n - ours) % n {
+            0 => Outcome::Draw,
+            ahead if ahead <= n / 2 => Outcome::Win,
+            _ => Outcome::Lose,
+        }
+    }
+
+    /// Our score for a round: our move's score plus the outcome's.
+    pub fn score(&self, ours: usize, theirs: usize) -> i64 {
+        self.moves[ours].score + self.outcomes[self.outcome(ours, theirs) as usize].1
+    }
+
+    /// Every move that reaches `outcome` against `theirs`, in circle order.
+    pub fn moves_for(
+        &self,
+        theirs: usize,
+        outcome: Outcome,
+    ) -> impl DoubleEndedIterator<Item = usize> + '_ {
+        (0..self.moves.len()).filter(move |&ours| self.outcome(ours, theirs) == outcome)
+    }
+
+    /// The move to play to reach `outcome` against `theirs`. Where more than
+    /// one does, the highest scoring, and the first of those in circle order.
+    pub fn pick(&self, theirs: usize, outcome: Outcome) -> usize {
+        self.moves_for(theirs, outcome)
+            .rev()
+            .max_by_key(|&ours| self.moves[ours].score)
+            .expect("every outcome is reachable from every move")
+    }
+
+    // Parses one round of a guide, giving the index of the move we play
+    fn parse_round(&self, line: &str, reading: Reading) -> Result<(usize, usize), ParseError> {
+        let mut tokens = line.split_whitespace();
+        let letters = |column: fn(&Move) -> &str| {
+            let letters: Vec<&str> = self.moves.iter().map(column).collect();
+            letters.join(", ")
+        };
+        let theirs = match tokens.next() {
+            Some(token) => self
+                .moves
+                .iter()
+                .position(|m| m.theirs == token)
+                .ok_or_else(|| ParseError::at(line, token, letters(|m| m.theirs.as_str())))?,
+            None => return Err(ParseError::at_end(line, letters(|m| m.theirs.as_str()))),
+        };
+        let expected = match reading {
+            Reading::Shape => letters(|m| m.ours.as_str()),
+            Reading::Outcome => {
+                let letters: Vec<&str> = self.outcomes.iter().map(|(l, _)| l.as_str()).collect();
+                letters.join(", ")
+            }
+        };
+        let token = tokens
+            .next()
+            .ok_or_else(|| ParseError::at_end(line, expected.as_str()))?;
+        let ours = match reading {
+            Reading::Shape => self.moves.iter().position(|m| m.ours == token),
+            Reading::Outcome => self
+                .outcomes
+                .iter()
+                .position(|(letter, _)| letter == token)
+                .map(|outcome| self.pick(theirs, Outcome::NAMES[outcome].1)),
+        }
+        .ok_or_else(|| ParseError::at(line, token, expected.as_str()))?;
+        if let Some(token) = tokens.next() {
+            return Err(ParseError::at(line, token, "end of line"));
+        }
+        Ok((ours, theirs))
+    }
+
+    /// Our total score following a strategy guide for this game, with its
+    /// second column read as `reading` says.
+    pub fn score_guide(&self, guide: &str, reading: Reading) -> Result<i64, ParseError> {
+        let rounds = parse_lines(guide, |line| self.parse_round(line, reading))?;
+        Ok(rounds
+            .into_iter()
+            .map(|(ours, theirs)| self.score(ours, theirs))
+            .sum())
+    }
+}
@@ -0,0 +1,201 @@
+use aoc_common::{parse_lines, Answer, Generate, ParseError, Rng, Solution};
+
+mod game;
+mod tournament;
+
+pub use game::{Game, Move, Outcome, Reading, CLASSIC};
+pub use tournament::{
+    outcome, play_match, round_robin, FixedGuide, FrequencyCounter, LastMoveBeater, Pairing,
+    Player, Random, Record,
+};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum RockPaperScissors {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl RockPaperScissors {
+    /// The shape this one beats.
+    pub fn beats(self) -> Self {
+        match self {
+            RockPaperScissors::Rock => RockPaperScissors::Scissors,
+            RockPaperScissors::Paper => RockPaperScissors::Rock,
+            RockPaperScissors::Scissors => RockPaperScissors::Paper,
+        }
+    }
+
+    /// The shape that beats this one.
+    pub fn beaten_by(self) -> Self {
+        self.beats().beats()
+    }
+}
+
+pub fn calc_score(ours: RockPaperScissors, theirs: RockPaperScissors) -> i32 {
+    match ours {
+        RockPaperScissors::Rock => match theirs {
+            RockPaperScissors::Rock => 1 + 3,
+            RockPaperScissors::Paper => 1,
+            RockPaperScissors::Scissors => 1 + 6,
+        },
+        RockPaperScissors::Paper => match theirs {
+            RockPaperScissors::Rock => 2 + 6,
+            RockPaperScissors::Paper => 2 + 3,
+            RockPaperScissors::Scissors => 2,
+        },
+        RockPaperScissors::Scissors => match theirs {
+            RockPaperScissors::Rock => 3,
+            RockPaperScissors::Paper => 3 + 6,
+            RockPaperScissors::Scissors => 3 + 3,
+        },
+    }
+}
+
+/// The guide's second column, which means nothing until a strategy reads it.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+/// A round of the guide as written: the opponent's shape and our response.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub theirs: RockPaperScissors,
+    pub response: Response,
+}
+
+/// A way of reading the guide's second column.
+pub trait Strategy {
+    /// The shape to play against `theirs` when the guide says `response`.
+    fn choose(&self, theirs: RockPaperScissors, response: Response) -> RockPaperScissors;
+}
+
+/// Part 1's reading: X, Y and Z are rock, paper and scissors.
+#[derive(Debug, Clone, Copy)]
+pub struct PlayShape;
+
+impl Strategy for PlayShape {
+    fn choose(&self, _: RockPaperScissors, response: Response) -> RockPaperScissors {
+        match response {
+            Response::X => RockPaperScissors::Rock,
+            Response::Y => RockPaperScissors::Paper,
+            Response::Z => RockPaperScissors::Scissors,
+        }
+    }
+}
+
+/// Part 2's reading: X, Y and Z say to lose, draw or win.
+#[derive(Debug, Clone, Copy)]
+pub struct ReachOutcome;
+
+impl Strategy for ReachOutcome {
+    fn choose(&self, theirs: RockPaperScissors, response: Response) -> RockPaperScissors {
+        match response {
+            Response::X => theirs.beats(),
+            Response::Y => theirs,
+            Response::Z => theirs.beaten_by(),
+        }
+    }
+}
+
+/// The strategies by the names they're chosen by: "shape" for part 1's and
+/// "outcome" for part 2's.
+pub fn strategy(name: &str) -> Option<&'static dyn Strategy> {
+    match name {
+        "shape" => Some(&PlayShape),
+        "outcome" => Some(&ReachOutcome),
+        _ => None,
+    }
+}
+
+// Checks one token of a round against the letters allowed in its column
+fn check_token<T: Copy>(
+    line: &str,
+    token: Option<&str>,
+    allowed: [(&str, T); 3],
+) -> Result<T, ParseError> {
+    let expected = format!("{}, {} or {}", allowed[0].0, allowed[1].0, allowed[2].0);
+    match token {
+        Some(token) => allowed
+            .iter()
+            .find(|(letter, _)| *letter == token)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| ParseError::at(line, token, expected)),
+        None => Err(ParseError::at_end(line, expected)),
+    }
+}
+
+// A round is the opponent's shape then the second column
+fn parse_round(line: &str) -> Result<Round, ParseError> {
+    let mut tokens = line.split_whitespace();
+    let theirs = check_token(
+        line,
+        tokens.next(),
+        [
+            ("A", RockPaperScissors::Rock),
+            ("B", RockPaperScissors::Paper),
+            ("C", RockPaperScissors::Scissors),
+        ],
+    )?;
+    let response = check_token(
+        line,
+        tokens.next(),
+        [("X", Response::X), ("Y", Response::Y), ("Z", Response::Z)],
+    )?;
+    if let Some(token) = tokens.next() {
+        return Err(ParseError::at(line, token, "end of line"));
+    }
+    Ok(Round { theirs, response })
+}
+
+#[derive(Debug)]
+pub struct StrategyGuide {
+    rounds: Vec<Round>,
+}
+
+impl StrategyGuide {
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// Our total score following the guide as `strategy` reads it.
+    pub fn score(&self, strategy: &dyn Strategy) -> i64 {
+        self.rounds
+            .iter()
+            .map(|round| calc_score(strategy.choose(round.theirs, round.response), round.theirs))
+            .map(i64::from)
+            .sum()
+    }
+}
+
+impl Solution for StrategyGuide {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            rounds: parse_lines(input, parse_round)?,
+        })
+    }
+    fn part1(&self) -> Answer {
+        self.score(&PlayShape).into()
+    }
+    fn part2(&self) -> Answer {
+        self.score(&ReachOutcome).into()
+    }
+}
+
+impl Generate for StrategyGuide {
+    // `size` rounds
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size.max(1))
+            .map(|_| {
+                format!(
+                    "{} {}\n",
+                    rng.pick(&['A', 'B', 'C']),
+                    rng.pick(&['X', 'Y', 'Z'])
+                )
+            })
+            .collect()
+    }
+}
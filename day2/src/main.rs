@@ -1,85 +1,120 @@
-use std::{
-    env,
-    fs::File,
-    io::{self, BufRead, BufReader},
-    path::Path,
+use std::{env, process};
+
+use aoc_common::{Input, Solution};
+use day2::{
+    round_robin, strategy, FixedGuide, FrequencyCounter, Game, LastMoveBeater, Player, Random,
+    Reading, StrategyGuide,
 };
 
-fn lines_from_file(filename: impl AsRef<Path>) -> io::Result<Vec<String>> {
-    BufReader::new(File::open(filename)?).lines().collect()
-}
+const USAGE: &str = "usage: day2 --strategy shape|outcome [--game <config file>] <input file | ->
+       day2 --tournament [--rounds <n>] [--seed <n>] [--strategy shape|outcome] <input file | ->";
+// Listed with the options for solving when those are given wrong
+const MODES: &[&str] = &[
+    "--strategy shape|outcome [--game <config file>] <input file | ->",
+    "--tournament [--rounds <n>] [--seed <n>] [--strategy shape|outcome] <input file | ->",
+];
 
-#[derive(Debug)]
-enum RockPaperScissors {
-    Rock,
-    Paper,
-    Scissors,
+fn read(path: &str) -> Result<String, String> {
+    Input::from(path).read().map_err(|err| err.to_string())
 }
 
-fn calc_score(ours: RockPaperScissors, theirs: RockPaperScissors) -> i32 {
-    match ours {
-        RockPaperScissors::Rock => match theirs {
-            RockPaperScissors::Rock => 1 + 3,
-            RockPaperScissors::Paper => 1 + 0,
-            RockPaperScissors::Scissors => 1 + 6,
-        },
-        RockPaperScissors::Paper => match theirs {
-            RockPaperScissors::Rock => 2 + 6,
-            RockPaperScissors::Paper => 2 + 3,
-            RockPaperScissors::Scissors => 2 + 0,
-        },
-        RockPaperScissors::Scissors => match theirs {
-            RockPaperScissors::Rock => 3 + 0,
-            RockPaperScissors::Paper => 3 + 6,
-            RockPaperScissors::Scissors => 3 + 3,
-        },
+// Scores the guide read one way only, in the puzzle's game or one set up from
+// a config file: `day2 --strategy shape|outcome [--game config] <input>`
+fn score(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let name = args.next().ok_or(USAGE)?;
+    let mut game = None;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--game" => game = Some(args.next().ok_or(USAGE)?),
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
     }
+    let path = path.ok_or(USAGE)?;
+    let input = read(&path)?;
+    let total = match game {
+        None => {
+            let strategy = strategy(&name).ok_or(USAGE)?;
+            let guide = StrategyGuide::parse(&input).map_err(|err| format!("{}: {}", path, err))?;
+            guide.score(strategy)
+        }
+        Some(config) => {
+            let reading = Reading::named(&name).ok_or(USAGE)?;
+            let game =
+                Game::parse(&read(&config)?).map_err(|err| format!("{}: {}", config, err))?;
+            game.score_guide(&input, reading)
+                .map_err(|err| format!("{}: {}", path, err))?
+        }
+    };
+    println!("{}", total);
+    Ok(())
 }
 
-fn convert_line(str: &String) -> (RockPaperScissors, RockPaperScissors) {
-    let tokens: Vec<&str> = str.split_whitespace().collect();
-
-    let first = match tokens[0] {
-        "A" => RockPaperScissors::Rock,
-        "B" => RockPaperScissors::Paper,
-        "C" => RockPaperScissors::Scissors,
-        _ => panic!("{}", tokens[0]),
-    };
-    let second = match tokens[1] {
-        "X" => match first {
-            // need to lose
-            RockPaperScissors::Rock => RockPaperScissors::Scissors,
-            RockPaperScissors::Paper => RockPaperScissors::Rock,
-            RockPaperScissors::Scissors => RockPaperScissors::Paper,
-        },
-        "Y" => match first {
-            // need to draw
-            RockPaperScissors::Rock => RockPaperScissors::Rock,
-            RockPaperScissors::Paper => RockPaperScissors::Paper,
-            RockPaperScissors::Scissors => RockPaperScissors::Scissors,
-        },
-        "Z" => match first {
-            // need to win
-            RockPaperScissors::Rock => RockPaperScissors::Paper,
-            RockPaperScissors::Paper => RockPaperScissors::Scissors,
-            RockPaperScissors::Scissors => RockPaperScissors::Rock,
-        },
-        _ => panic!("{}", tokens[0]),
+// Plays the guide, as the strategy reads it, and the other players against
+// each other: `day2 --tournament [--rounds n] [--seed n] [--strategy name] <input>`
+fn tournament(mut args: impl Iterator<Item = String>) -> Result<(), String> {
+    let mut rounds = 1000;
+    let mut seed = 0;
+    let mut name = "outcome".to_string();
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(USAGE);
+        match arg.as_str() {
+            "--rounds" => rounds = value()?.parse().map_err(|_| USAGE)?,
+            "--seed" => seed = value()?.parse().map_err(|_| USAGE)?,
+            "--strategy" => name = value()?,
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let path = path.ok_or(USAGE)?;
+    let strategy = strategy(&name).ok_or(USAGE)?;
+    let guide = StrategyGuide::parse(&read(&path)?).map_err(|err| format!("{}: {}", path, err))?;
+    if guide.rounds().is_empty() {
+        return Err(format!("{}: the guide has no rounds to play", path));
+    }
+    let players = || -> Vec<Box<dyn Player>> {
+        vec![
+            Box::new(FixedGuide::from_guide(&guide, strategy)),
+            Box::new(Random),
+            Box::new(FrequencyCounter::default()),
+            Box::new(LastMoveBeater::default()),
+        ]
     };
-    (first, second)
+    for pairing in round_robin(players, rounds, seed) {
+        let record = pairing.record;
+        println!(
+            "{} v {}: won {:.1}%, drew {:.1}%, lost {:.1}%, scoring {:.2} to {:.2} a round",
+            pairing.ours,
+            pairing.theirs,
+            record.win_rate() * 100.0,
+            record.draw_rate() * 100.0,
+            record.loss_rate() * 100.0,
+            record.expected_score(),
+            record.their_expected_score()
+        );
+    }
+    Ok(())
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let filename = &args[1];
-    let lines = lines_from_file(filename).expect("Could not load");
-    let mut total = 0;
-
-    for line in lines {
-        let (theirs, ours) = convert_line(&line);
-        let score = calc_score(ours, theirs);
-        total += score;
-        println!("{} {} {}", line, score, total)
+    let mut args = env::args().skip(1).peekable();
+    if args.peek().map(String::as_str) == Some("--strategy") {
+        args.next();
+        if let Err(err) = score(args) {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+        return;
+    }
+    if args.peek().map(String::as_str) == Some("--tournament") {
+        args.next();
+        if let Err(err) = tournament(args) {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+        return;
     }
+    aoc_common::run_with_modes::<StrategyGuide>(2, MODES);
 }
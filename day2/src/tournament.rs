@@ -0,0 +1,235 @@
+use aoc_common::Rng;
+
+use crate::{calc_score, Outcome, RockPaperScissors, Strategy, StrategyGuide};
+
+const SHAPES: [RockPaperScissors; 3] = [
+    RockPaperScissors::Rock,
+    RockPaperScissors::Paper,
+    RockPaperScissors::Scissors,
+];
+
+/// Someone to play rock, paper, scissors against, round after round.
+pub trait Player {
+    fn name(&self) -> &'static str;
+    /// The shape to play next, with `rng` for any chance in it.
+    fn play(&mut self, rng: &mut Rng) -> RockPaperScissors;
+    /// Learns what the other player played once a round is over.
+    fn observe(&mut self, _theirs: RockPaperScissors) {}
+}
+
+/// Plays the shapes a strategy guide says, in order, starting over at the end.
+#[derive(Debug, Clone)]
+pub struct FixedGuide {
+    shapes: Vec<RockPaperScissors>,
+    next: usize,
+}
+
+impl FixedGuide {
+    /// Plays `shapes`, which mustn't be empty.
+    pub fn new(shapes: Vec<RockPaperScissors>) -> Self {
+        assert!(!shapes.is_empty(), "a guide needs a round to play");
+        Self { shapes, next: 0 }
+    }
+
+    /// Plays the shapes `strategy` reads from `guide`. They're chosen against
+    /// the moves the guide expects, whatever the opponent really plays.
+    pub fn from_guide(guide: &StrategyGuide, strategy: &dyn Strategy) -> Self {
+        Self::new(
+            guide
+                .rounds()
+                .iter()
+                .map(|round| strategy.choose(round.theirs, round.response))
+                .collect(),
+        )
+    }
+}
+
+impl Player for FixedGuide {
+    fn name(&self) -> &'static str {
+        "fixed guide"
+    }
+    fn play(&mut self, _: &mut Rng) -> RockPaperScissors {
+        let shape = self.shapes[self.next];
+        self.next = (self.next + 1) % self.shapes.len();
+        shape
+    }
+}
+
+/// Plays any shape, each as likely as the others.
+#[derive(Debug, Clone, Copy)]
+pub struct Random;
+
+impl Player for Random {
+    fn name(&self) -> &'static str {
+        "random"
+    }
+    fn play(&mut self, rng: &mut Rng) -> RockPaperScissors {
+        *rng.pick(&SHAPES)
+    }
+}
+
+/// Plays whatever beats the shape the other player has played most, or any
+/// shape before they've played at all. Of shapes played equally often, rock
+/// counts as most, then paper.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FrequencyCounter {
+    counts: [usize; 3],
+}
+
+impl Player for FrequencyCounter {
+    fn name(&self) -> &'static str {
+        "frequency counter"
+    }
+    fn play(&mut self, rng: &mut Rng) -> RockPaperScissors {
+        if self.counts == [0; 3] {
+            return *rng.pick(&SHAPES);
+        }
+        let most = (0..3).rev().max_by_key(|&i| self.counts[i]).unwrap();
+        SHAPES[most].beaten_by()
+    }
+    fn observe(&mut self, theirs: RockPaperScissors) {
+        self.counts[theirs as usize] += 1;
+    }
+}
+
+/// Plays whatever beats the other player's last shape, or any shape first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LastMoveBeater {
+    last: Option<RockPaperScissors>,
+}
+
+impl Player for LastMoveBeater {
+    fn name(&self) -> &'static str {
+        "last move beater"
+    }
+    fn play(&mut self, rng: &mut Rng) -> RockPaperScissors {
+        match self.last {
+            Some(last) => last.beaten_by(),
+            None => *rng.pick(&SHAPES),
+        }
+    }
+    fn observe(&mut self, theirs: RockPaperScissors) {
+        self.last = Some(theirs);
+    }
+}
+
+/// How a match went for the first player, with both players' scores.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Record {
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    pub score: i64,
+    pub their_score: i64,
+}
+
+impl Record {
+    pub fn rounds(&self) -> usize {
+        self.wins + self.draws + self.losses
+    }
+
+    fn rate(&self, count: usize) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => count as f64 / rounds as f64,
+        }
+    }
+
+    pub fn win_rate(&self) -> f64 {
+        self.rate(self.wins)
+    }
+
+    pub fn draw_rate(&self) -> f64 {
+        self.rate(self.draws)
+    }
+
+    pub fn loss_rate(&self) -> f64 {
+        self.rate(self.losses)
+    }
+
+    /// The first player's score a round, 0 before any rounds.
+    pub fn expected_score(&self) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => self.score as f64 / rounds as f64,
+        }
+    }
+
+    /// The second player's score a round, 0 before any rounds.
+    pub fn their_expected_score(&self) -> f64 {
+        match self.rounds() {
+            0 => 0.0,
+            rounds => self.their_score as f64 / rounds as f64,
+        }
+    }
+}
+
+/// Plays `rounds` rounds between two players, scoring each with
+/// `calc_score`. All the chance comes from `rng`, so a match played again
+/// from the same seed goes the same way.
+pub fn play_match(
+    ours: &mut dyn Player,
+    theirs: &mut dyn Player,
+    rounds: usize,
+    rng: &mut Rng,
+) -> Record {
+    let mut record = Record::default();
+    for _ in 0..rounds {
+        let our_shape = ours.play(rng);
+        let their_shape = theirs.play(rng);
+        ours.observe(their_shape);
+        theirs.observe(our_shape);
+        record.score += i64::from(calc_score(our_shape, their_shape));
+        record.their_score += i64::from(calc_score(their_shape, our_shape));
+        match outcome(our_shape, their_shape) {
+            Outcome::Win => record.wins += 1,
+            Outcome::Draw => record.draws += 1,
+            Outcome::Lose => record.losses += 1,
+        }
+    }
+    record
+}
+
+/// How our shape fares against theirs.
+pub fn outcome(ours: RockPaperScissors, theirs: RockPaperScissors) -> Outcome {
+    if ours == theirs {
+        Outcome::Draw
+    } else if ours.beats() == theirs {
+        Outcome::Win
+    } else {
+        Outcome::Lose
+    }
+}
+
+/// One match of a round robin, with the players named.
+#[derive(Debug, Clone, Copy)]
+pub struct Pairing {
+    pub ours: &'static str,
+    pub theirs: &'static str,
+    pub record: Record,
+}
+
+/// Plays every player once against every other, made fresh for each match by
+/// `players` so none carries what it learned into the next. Each match is
+/// played from `seed`, so it goes the same way whatever else is played.
+pub fn round_robin(
+    players: impl Fn() -> Vec<Box<dyn Player>>,
+    rounds: usize,
+    seed: u64,
+) -> Vec<Pairing> {
+    let count = players().len();
+    let mut pairings = Vec::new();
+    for i in 0..count {
+        for j in i + 1..count {
+            let mut ours = players().swap_remove(i);
+            let mut theirs = players().swap_remove(j);
+            let record = play_match(ours.as_mut(), theirs.as_mut(), rounds, &mut Rng::new(seed));
+            pairings.push(Pairing {
+                ours: ours.name(),
+                theirs: theirs.name(),
+                record,
+            });
+        }
+    }
+    pairings
+}
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day2::StrategyGuide;
+
+#[test]
+fn sample() {
+    check::<StrategyGuide>("sample.txt", 15, 12);
+}
+
+#[test]
+fn input() {
+    check_input::<StrategyGuide>("input.txt", 10_816, 11_657);
+}
+
+#[test]
+fn generated() {
+    check_generated::<StrategyGuide>(100, 3);
+}
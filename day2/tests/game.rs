@@ -0,0 +1,110 @@
+use day2::{calc_score, Game, Outcome, Reading, RockPaperScissors};
+use std::fs;
+
+const SAMPLE: &str = "A Y\nB X\nC Z\n";
+
+fn game(name: &str) -> Game {
+    Game::parse(&fs::read_to_string(format!("games/{}.txt", name)).unwrap()).unwrap()
+}
+
+#[test]
+fn classic_matches_calc_score() {
+    let game = Game::classic();
+    let shape = |name| match name {
+        "rock" => RockPaperScissors::Rock,
+        "paper" => RockPaperScissors::Paper,
+        _ => RockPaperScissors::Scissors,
+    };
+    for (ours, our_move) in game.moves().iter().enumerate() {
+        for (theirs, their_move) in game.moves().iter().enumerate() {
+            assert_eq!(
+                game.score(ours, theirs),
+                i64::from(calc_score(shape(&our_move.name), shape(&their_move.name)))
+            );
+        }
+    }
+    assert_eq!(game.score_guide(SAMPLE, Reading::Shape), Ok(15));
+    assert_eq!(game.score_guide(SAMPLE, Reading::Outcome), Ok(12));
+}
+
+#[test]
+fn each_move_beats_half_the_circle() {
+    for (name, n) in [("rps", 3), ("rpsls", 5), ("rps7", 7)] {
+        let game = game(name);
+        assert_eq!(game.moves().len(), n);
+        for theirs in 0..n {
+            assert_eq!(game.moves_for(theirs, Outcome::Win).count(), n / 2);
+            assert_eq!(game.moves_for(theirs, Outcome::Lose).count(), n / 2);
+            assert_eq!(
+                game.moves_for(theirs, Outcome::Draw).collect::<Vec<_>>(),
+                [theirs]
+            );
+        }
+    }
+}
+
+#[test]
+fn rpsls() {
+    let game = game("rpsls");
+    let index = |name| game.index(name).unwrap();
+    assert_eq!(game.outcome(index("spock"), index("rock")), Outcome::Win);
+    assert_eq!(game.outcome(index("lizard"), index("spock")), Outcome::Win);
+    assert_eq!(
+        game.outcome(index("paper"), index("scissors")),
+        Outcome::Lose
+    );
+    // Lizard scores 4 against rock's 1
+    assert_eq!(game.score_guide(SAMPLE, Reading::Shape), Ok(4 + 9 + 11));
+}
+
+#[test]
+fn pick_for_outcome() {
+    let game = game("rps7");
+    let rock = game.index("rock").unwrap();
+    // Paper, air and water all beat rock, and water scores most
+    assert_eq!(game.pick(rock, Outcome::Win), game.index("water").unwrap());
+    assert_eq!(game.pick(rock, Outcome::Draw), rock);
+    assert_eq!(
+        game.pick(rock, Outcome::Lose),
+        game.index("sponge").unwrap()
+    );
+    for theirs in 0..game.moves().len() {
+        for outcome in [Outcome::Lose, Outcome::Draw, Outcome::Win] {
+            assert_eq!(game.outcome(game.pick(theirs, outcome), theirs), outcome);
+        }
+    }
+}
+
+#[test]
+fn rejects_bad_configs() {
+    let outcomes = "lose X 0\ndraw Y 3\nwin Z 6\n";
+    let moves = "move rock A X 1\nmove scissors C Z 3\nmove paper B Y 2\n";
+    assert!(Game::parse(&format!("{}{}", moves, outcomes)).is_ok());
+    // An even number of moves can't go round evenly
+    let even = format!("{}move spock D W 5\n{}", moves, outcomes);
+    assert!(Game::parse(&even).is_err());
+    let repeated = format!("{}move rock D W 5\nmove spock E V 6\n{}", moves, outcomes);
+    assert_eq!(Game::parse(&repeated).unwrap_err().line, 4);
+    assert!(Game::parse(moves).is_err());
+    assert_eq!(
+        Game::parse(&format!("{}lose X 0\nwin Z nine\n", moves))
+            .unwrap_err()
+            .column,
+        7
+    );
+    assert!(Game::parse(&format!("{}{}draw Y 3\n", moves, outcomes)).is_err());
+}
+
+#[test]
+fn rejects_bad_rounds() {
+    let game = game("rpsls");
+    assert!(game.score_guide("F X\n", Reading::Shape).is_err());
+    assert!(game.score_guide("A V\n", Reading::Outcome).is_err());
+    assert_eq!(game.score_guide("A V\n", Reading::Shape), Ok(1 + 3));
+    assert_eq!(
+        game.score_guide("A V\nA\n", Reading::Shape)
+            .unwrap_err()
+            .line,
+        2
+    );
+}
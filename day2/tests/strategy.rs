@@ -0,0 +1,60 @@
+use aoc_common::Solution;
+use day2::{
+    calc_score, strategy, PlayShape, ReachOutcome, Response, RockPaperScissors, Round, Strategy,
+    StrategyGuide,
+};
+
+const SAMPLE: &str = "A Y\nB X\nC Z\n";
+
+#[test]
+fn parses_neutral_rounds() {
+    let guide = StrategyGuide::parse(SAMPLE).unwrap();
+    assert_eq!(
+        guide.rounds()[0],
+        Round {
+            theirs: RockPaperScissors::Rock,
+            response: Response::Y,
+        }
+    );
+    assert_eq!(guide.rounds().len(), 3);
+}
+
+#[test]
+fn both_readings() {
+    let guide = StrategyGuide::parse(SAMPLE).unwrap();
+    assert_eq!(guide.score(&PlayShape), 15);
+    assert_eq!(guide.score(&ReachOutcome), 12);
+}
+
+#[test]
+fn reach_outcome() {
+    for theirs in [
+        RockPaperScissors::Rock,
+        RockPaperScissors::Paper,
+        RockPaperScissors::Scissors,
+    ] {
+        let score = |response| {
+            let ours = ReachOutcome.choose(theirs, response);
+            calc_score(ours, theirs) - calc_score(ours, ours) + 3
+        };
+        assert_eq!(score(Response::X), 0);
+        assert_eq!(score(Response::Y), 3);
+        assert_eq!(score(Response::Z), 6);
+    }
+}
+
+#[test]
+fn by_name() {
+    let guide = StrategyGuide::parse(SAMPLE).unwrap();
+    assert_eq!(guide.score(strategy("shape").unwrap()), 15);
+    assert_eq!(guide.score(strategy("outcome").unwrap()), 12);
+    assert!(strategy("rock").is_none());
+}
+
+#[test]
+fn rejects_bad_rounds() {
+    assert!(StrategyGuide::parse("A W\n").is_err());
+    assert!(StrategyGuide::parse("D X\n").is_err());
+    assert!(StrategyGuide::parse("A\n").is_err());
+    assert!(StrategyGuide::parse("A X Y\n").is_err());
+}
@@ -0,0 +1,80 @@
+use aoc_common::{Rng, Solution};
+use day2::{
+    outcome, play_match, round_robin, FixedGuide, FrequencyCounter, LastMoveBeater, Outcome,
+    Player, Random, ReachOutcome, RockPaperScissors, StrategyGuide,
+};
+
+use RockPaperScissors::{Paper, Rock, Scissors};
+
+fn players() -> Vec<Box<dyn Player>> {
+    let guide = StrategyGuide::parse("A Y\nB X\nC Z\n").unwrap();
+    vec![
+        Box::new(FixedGuide::from_guide(&guide, &ReachOutcome)),
+        Box::new(Random),
+        Box::new(FrequencyCounter::default()),
+        Box::new(LastMoveBeater::default()),
+    ]
+}
+
+#[test]
+fn outcomes() {
+    assert_eq!(outcome(Rock, Scissors), Outcome::Win);
+    assert_eq!(outcome(Rock, Paper), Outcome::Lose);
+    assert_eq!(outcome(Paper, Paper), Outcome::Draw);
+}
+
+#[test]
+fn fixed_guides_score_as_calc_score_does() {
+    // Rock against paper, scissors against rock, each 10 times
+    let mut ours = FixedGuide::new(vec![Rock, Scissors]);
+    let mut theirs = FixedGuide::new(vec![Paper, Rock]);
+    let record = play_match(&mut ours, &mut theirs, 20, &mut Rng::new(0));
+    assert_eq!(record.rounds(), 20);
+    assert_eq!(record.losses, 20);
+    assert_eq!(record.score, 10 * (1 + 3));
+    assert_eq!(record.their_score, 10 * (2 + 6 + 1 + 6));
+    assert_eq!(record.loss_rate(), 1.0);
+    assert_eq!(record.expected_score(), 2.0);
+}
+
+#[test]
+fn opponent_models() {
+    // Always rock: both models catch on after a round and play paper
+    let mut rock = FixedGuide::new(vec![Rock]);
+    let record = play_match(
+        &mut FrequencyCounter::default(),
+        &mut rock,
+        100,
+        &mut Rng::new(1),
+    );
+    assert!(record.wins >= 99);
+    let record = play_match(
+        &mut LastMoveBeater::default(),
+        &mut rock,
+        100,
+        &mut Rng::new(1),
+    );
+    assert!(record.wins >= 99);
+}
+
+#[test]
+fn random_is_fair() {
+    let record = play_match(&mut Random, &mut Random, 30_000, &mut Rng::new(2));
+    for rate in [record.win_rate(), record.draw_rate(), record.loss_rate()] {
+        assert!((rate - 1.0 / 3.0).abs() < 0.02, "{:?}", record);
+    }
+    assert!((record.expected_score() - 5.0).abs() < 0.1, "{:?}", record);
+}
+
+#[test]
+fn seeded() {
+    let first = round_robin(players, 500, 7);
+    let again = round_robin(players, 500, 7);
+    assert_eq!(first.len(), 6);
+    for (a, b) in first.iter().zip(&again) {
+        assert_eq!((a.ours, a.theirs, a.record), (b.ours, b.theirs, b.record));
+        assert_eq!(a.record.rounds(), 500);
+    }
+    assert_eq!(first[0].ours, "fixed guide");
+    assert_eq!(first[5].theirs, "last move beater");
+}
@@ -1,3 +1,4 @@
+use aoc_common::{number, parse_lines, Answer, Generate, ParseError, Rng, Solution};
 use std::cell::RefCell;
 use std::rc::Rc;
 
@@ -7,32 +8,32 @@ struct Node {
 }
 
 pub struct Puzzle {
-    order: Vec<Rc<Node>>,
-    list: Vec<Rc<Node>>,
+    values: Vec<i64>,
 }
 
 const KEY: i64 = 811589153;
 
+impl Default for Puzzle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Puzzle {
     pub fn new() -> Puzzle {
-        Self {
-            order: Vec::new(),
-            list: Vec::new(),
-        }
+        Self { values: Vec::new() }
     }
-    pub fn add_line(&mut self, line: &str) {
-        let index = self.order.len();
-        let value: i64 = line.parse().unwrap();
-        let node = Rc::new(Node {
-            value: value * KEY,
-            index: RefCell::new(index),
-        });
-        self.order.push(node.clone());
-        self.list.push(node);
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
+        self.values.push(number(line, line.trim())?);
+        Ok(())
     }
 
-    fn mix(order: &Vec<Rc<Node>>, list: &mut Vec<Rc<Node>>) {
+    fn mix(order: &[Rc<Node>], list: &mut Vec<Rc<Node>>) {
         let wrap = order.len() as i64 - 1;
+        // A lone number has nowhere to move to
+        if wrap < 1 {
+            return;
+        }
         for node in order {
             let mut old_i = node.index.borrow_mut();
             let mut new_i = (*old_i as i64 + node.value) % wrap;
@@ -42,40 +43,90 @@ impl Puzzle {
             let new_i = new_i as usize;
 
             if new_i < *old_i {
-                for i in new_i..*old_i {
-                    list[i].index.replace_with(|&mut old| old + 1);
+                for moved in &list[new_i..*old_i] {
+                    moved.index.replace_with(|&mut old| old + 1);
                 }
             } else if *old_i < new_i {
-                for i in (*old_i + 1)..=new_i {
-                    list[i].index.replace_with(|&mut old| old - 1);
+                for moved in &list[(*old_i + 1)..=new_i] {
+                    moved.index.replace_with(|&mut old| old - 1);
                 }
             }
 
             let to_move = list.remove(*old_i);
             *old_i = new_i;
-            list.insert(new_i as usize, to_move);
+            list.insert(new_i, to_move);
         }
     }
 
-    fn score(list: &Vec<Rc<Node>>) -> i64 {
+    fn score(list: &[i64]) -> i64 {
         let pos0 = list
             .iter()
-            .position(|e| e.value == 0)
+            .position(|&value| value == 0)
             .expect("List should contain a 0");
         let n = list.len();
 
-        list[(pos0 + 1000) % n].value
-            + list[(pos0 + 2000) % n].value
-            + list[(pos0 + 3000) % n].value
+        list[(pos0 + 1000) % n] + list[(pos0 + 2000) % n] + list[(pos0 + 3000) % n]
+    }
+
+    /// The numbers, each multiplied by `key`, after mixing `rounds` times.
+    /// The list is circular, so it starts wherever mixing left it.
+    pub fn mixed(&self, key: i64, rounds: usize) -> Vec<i64> {
+        let order: Vec<Rc<Node>> = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                Rc::new(Node {
+                    value: value * key,
+                    index: RefCell::new(index),
+                })
+            })
+            .collect();
+        let mut list = order.clone();
+
+        for _ in 0..rounds {
+            Self::mix(&order, &mut list);
+        }
+        list.iter().map(|node| node.value).collect()
     }
 
-    pub fn pt_1(&mut self) -> i32 {
-        0
+    fn decrypt(&self, key: i64, rounds: usize) -> i64 {
+        Self::score(&self.mixed(key, rounds))
     }
-    pub fn pt_2(&mut self) -> i64 {
-        for _ in 0..10 {
-            Self::mix(&self.order, &mut self.list);
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut puzzle = Puzzle::new();
+        parse_lines(input, |line| puzzle.add_line(line))?;
+        // The grove coordinates are counted from the 0
+        if !puzzle.values.contains(&0) {
+            return Err(ParseError::at_end(input, "a 0 somewhere in the file"));
         }
-        Self::score(&self.list)
+        Ok(puzzle)
+    }
+    fn part1(&self) -> Answer {
+        self.decrypt(1, 1).into()
+    }
+    fn part2(&self) -> Answer {
+        self.decrypt(KEY, 10).into()
+    }
+}
+
+impl Generate for Puzzle {
+    // `size` numbers, exactly one of them 0
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut numbers: Vec<i64> = (1..size.max(1))
+            .map(|_| match rng.range(-10_000..10_000) {
+                0 => 10_000,
+                number => number,
+            })
+            .collect();
+        numbers.push(0);
+        rng.shuffle(&mut numbers);
+        numbers
+            .iter()
+            .map(|number| format!("{}\n", number))
+            .collect()
     }
 }
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day20::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 3, 1_623_178_306);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 14_888, 3_760_092_545_849_i64);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(100, 3);
+}
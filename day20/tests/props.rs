@@ -0,0 +1,31 @@
+use aoc_common::Solution;
+use day20::Puzzle;
+use proptest::prelude::*;
+
+// A file of numbers always holding the 0 the answers are counted from
+fn numbers() -> impl Strategy<Value = Vec<i64>> {
+    prop::collection::vec(-10_000..10_000_i64, 0..40).prop_flat_map(|values| {
+        let len = values.len();
+        (0..=len).prop_map(move |at| {
+            let mut values = values.clone();
+            values.insert(at, 0);
+            values
+        })
+    })
+}
+
+fn sorted(mut values: Vec<i64>) -> Vec<i64> {
+    values.sort_unstable();
+    values
+}
+
+proptest! {
+    #[test]
+    fn mixing_keeps_the_numbers(values in numbers(), key in 1..1_000_i64, rounds in 1..4_usize) {
+        let input: String = values.iter().map(|value| format!("{}\n", value)).collect();
+        let puzzle = Puzzle::parse(&input).unwrap();
+        let mixed = puzzle.mixed(key, rounds);
+        let expected = values.iter().map(|value| value * key).collect();
+        prop_assert_eq!(sorted(mixed), sorted(expected));
+    }
+}
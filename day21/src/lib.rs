@@ -1,12 +1,14 @@
+use aoc_common::{parse_lines, Answer, Generate, ParseError, Rng, Solution};
 use nom::{
     branch::alt,
     bytes::complete::tag,
     character::complete::{alpha1, digit1, one_of, space0},
-    combinator::map,
+    combinator::{map, map_res},
     sequence::tuple,
     IResult,
 };
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use tracing::debug;
 
 #[derive(Debug)]
 enum Monkey {
@@ -17,13 +19,14 @@ enum Monkey {
     Num(i64),
 }
 
+#[derive(Debug)]
 pub struct Puzzle {
     monkeys: HashMap<String, Monkey>,
 }
 
 fn parse_operation(input: &str) -> IResult<&str, Monkey> {
     alt((
-        map(digit1, |s: &str| Monkey::Num(s.parse().unwrap())),
+        map(map_res(digit1, str::parse), Monkey::Num),
         map(
             tuple((alpha1, space0, one_of("+-*/"), space0, alpha1)),
             |t: (&str, &str, char, &str, &str)| match t.2 {
@@ -36,12 +39,25 @@ fn parse_operation(input: &str) -> IResult<&str, Monkey> {
         ),
     ))(input)
 }
-fn parser(input: &str) -> (&str, Monkey) {
+fn parser(input: &str) -> Result<(&str, Monkey), ParseError> {
     let result: IResult<&str, (&str, &str, Monkey)> =
         tuple((alpha1, tag(": "), parse_operation))(input);
 
-    let unwrapped = result.unwrap().1;
-    return (unwrapped.0, unwrapped.2);
+    let expected = "<name>: <n> or <name>: <name> <op> <name>";
+    match result {
+        Ok(("", (name, _, monkey))) => Ok((name, monkey)),
+        Ok((rest, _)) => Err(ParseError::at(input, rest, "end of line")),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+            Err(ParseError::at(input, err.input, expected))
+        }
+        Err(nom::Err::Incomplete(_)) => Err(ParseError::at_end(input, expected)),
+    }
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Puzzle {
@@ -50,9 +66,10 @@ impl Puzzle {
             monkeys: HashMap::new(),
         }
     }
-    pub fn add_line(&mut self, line: &str) {
-        let (name, data) = parser(line);
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
+        let (name, data) = parser(line)?;
         self.monkeys.insert(name.to_string(), data);
+        Ok(())
     }
     fn get_answer(&self, name: &str) -> i64 {
         match self.monkeys.get(name).unwrap() {
@@ -64,10 +81,6 @@ impl Puzzle {
         }
     }
 
-    pub fn pt_1(&self) -> i64 {
-        self.get_answer("root")
-    }
-
     fn find_human_path(&self, name: &str) -> Option<Vec<String>> {
         if name == "humn" {
             return Some(vec![name.to_string()]);
@@ -119,10 +132,46 @@ impl Puzzle {
         };
         self.solve(human_path, to_solve, sub_answer)
     }
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut puzzle = Puzzle::new();
+        parse_lines(input, |line| puzzle.add_line(line))?;
 
-    pub fn pt_2(&mut self) -> i64 {
-        let mut human_path = self.find_human_path("root").unwrap();
-        println!("{:?}", human_path);
+        // Every monkey waited on has to shout something
+        for line in input.lines() {
+            let (_, job) = line.split_once(": ").unwrap();
+            for token in job.split_whitespace() {
+                if token.chars().all(char::is_alphabetic) && !puzzle.monkeys.contains_key(token) {
+                    return Err(ParseError::at(input, token, "a monkey with a job"));
+                }
+            }
+        }
+        match puzzle.monkeys.get("root") {
+            None => return Err(ParseError::at_end(input, "a monkey named root")),
+            Some(Monkey::Num(_)) => {
+                // The last job given is the one kept
+                let line = input.lines().rev().find(|line| line.starts_with("root:"));
+                let job = &line.unwrap()["root: ".len()..];
+                return Err(ParseError::at(input, job, "root to compare two monkeys"));
+            }
+            Some(_) => {}
+        }
+        if !puzzle.monkeys.contains_key("humn") {
+            return Err(ParseError::at_end(input, "a monkey named humn"));
+        }
+        Ok(puzzle)
+    }
+    fn part1(&self) -> Answer {
+        self.get_answer("root").into()
+    }
+    fn part2(&self) -> Answer {
+        // Nothing humn shouts changes root's test if root doesn't wait on it
+        let Some(mut human_path) = self.find_human_path("root") else {
+            return Answer::None;
+        };
+        debug!(?human_path, "path to humn");
         human_path.pop();
         let (left, right) = match self.monkeys.get("root").unwrap() {
             Monkey::Add(left, right)
@@ -133,10 +182,154 @@ impl Puzzle {
         };
         if left == &human_path.pop().unwrap() {
             let right_result = self.get_answer(right);
-            self.solve(&mut human_path, left, right_result)
+            self.solve(&mut human_path, left, right_result).into()
         } else {
             let left_result = self.get_answer(left);
-            self.solve(&mut human_path, right, left_result)
+            self.solve(&mut human_path, right, left_result).into()
+        }
+    }
+}
+
+// Where a made up monkey stands: its name, what it shouts when humn shouts
+// the number in the input and when humn shouts the answer, and whether it
+// waits on humn at all
+#[derive(Clone)]
+struct Shout {
+    name: String,
+    given: i64,
+    answer: i64,
+    human: bool,
+}
+
+// The monkeys made up so far, each with its own name, and their jobs
+#[derive(Default)]
+struct Troop {
+    names: HashSet<String>,
+    lines: Vec<String>,
+}
+
+impl Troop {
+    fn new_name(&mut self, rng: &mut Rng) -> String {
+        loop {
+            let name: String = (0..4)
+                .map(|_| (b'a' + rng.below(26) as u8) as char)
+                .collect();
+            if !["root", "humn"].contains(&name.as_str()) && self.names.insert(name.clone()) {
+                return name;
+            }
+        }
+    }
+    fn leaf(&mut self, rng: &mut Rng, value: i64) -> Shout {
+        let name = self.new_name(rng);
+        self.lines.push(format!("{}: {}", name, value));
+        Shout {
+            name,
+            given: value,
+            answer: value,
+            human: false,
+        }
+    }
+}
+
+// How far from zero any monkey's shout may go, to leave plenty of room
+// before i64 overflows
+const LIMIT: i64 = 1_000_000_000_000;
+
+impl Generate for Puzzle {
+    // About `size` monkeys, at least five, waiting on each other in a tree
+    // with root at the top. Each shout is worked out as the monkeys are
+    // made, both for humn's number and for the answer, so every division
+    // is exact for the answer and nothing overflows. humn is never a
+    // divisor, as in the real inputs.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut troop = Troop::default();
+        let given = rng.range(1..1_000);
+        troop.lines.push(format!("humn: {}", given));
+        let mut pool = vec![Shout {
+            name: "humn".to_string(),
+            given,
+            answer: rng.range(1..1_000),
+            human: true,
+        }];
+        for _ in 1..(size.max(5) / 2).max(2) {
+            let value = rng.range(1..20);
+            pool.push(troop.leaf(rng, value));
+        }
+        while pool.len() > 2 {
+            let (a, b) = (rng.below(pool.len()), rng.below(pool.len()));
+            if a == b {
+                continue;
+            }
+            let (left, right) = (&pool[a], &pool[b]);
+            let mut ops = ['+', '-', '*', '/'];
+            rng.shuffle(&mut ops);
+            let shout = ops.iter().find_map(|&op| {
+                let apply = |l: i64, r: i64| match op {
+                    '+' => l.checked_add(r),
+                    '-' => l.checked_sub(r),
+                    '*' => l.checked_mul(r),
+                    _ => (r != 0 && l % r == 0).then(|| l / r),
+                };
+                let usable = match op {
+                    '*' => !(left.human && right.answer == 0 || right.human && left.answer == 0),
+                    '/' => !right.human && right.given != 0,
+                    _ => true,
+                };
+                let given = match op {
+                    '/' if usable => Some(left.given / right.given),
+                    _ => apply(left.given, right.given),
+                };
+                let answer = apply(left.answer, right.answer);
+                match (usable, given, answer) {
+                    (true, Some(given), Some(answer))
+                        if given.abs() <= LIMIT && answer.abs() <= LIMIT =>
+                    {
+                        Some((op, given, answer))
+                    }
+                    _ => None,
+                }
+            });
+            let Some((op, given, answer)) = shout else {
+                continue;
+            };
+            let name = troop.new_name(rng);
+            troop
+                .lines
+                .push(format!("{}: {} {} {}", name, left.name, op, right.name));
+            let shout = Shout {
+                name,
+                given,
+                answer,
+                human: left.human || right.human,
+            };
+            let (high, low) = (a.max(b), a.min(b));
+            pool.swap_remove(high);
+            pool[low] = shout;
+        }
+
+        // Root's other side has to come to what the human side does for the
+        // answer
+        if pool[0].human {
+            pool.swap(0, 1);
         }
+        let (other, human) = (&pool[0], &pool[1]);
+        let gap = human.answer - other.answer;
+        let fill = troop.leaf(rng, gap.abs());
+        let name = troop.new_name(rng);
+        let op = if gap >= 0 { '+' } else { '-' };
+        troop
+            .lines
+            .push(format!("{}: {} {} {}", name, other.name, op, fill.name));
+        let mut sides = [name, human.name.clone()];
+        rng.shuffle(&mut sides);
+        troop
+            .lines
+            .push(format!("root: {} + {}", sides[0], sides[1]));
+        rng.shuffle(&mut troop.lines);
+        troop
+            .lines
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect()
     }
 }
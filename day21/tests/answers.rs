@@ -0,0 +1,31 @@
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day21::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 152, 301);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 84_244_467_642_604_i64, 3_759_569_926_192_i64);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(100, 3);
+}
+
+#[test]
+fn rejects_troops_without_humn_or_a_test_at_root() {
+    let err = Puzzle::parse("root: 5\nhumn: 3\n").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 7));
+    assert!(Puzzle::parse("root: abcd + efgh\nabcd: 1\nefgh: 2\n").is_err());
+}
+
+#[test]
+fn root_not_waiting_on_humn() {
+    let puzzle = Puzzle::parse("root: abcd + efgh\nabcd: 1\nefgh: 2\nhumn: 3\n").unwrap();
+    assert_eq!(puzzle.part1(), Answer::Num(3));
+    assert_eq!(puzzle.part2(), Answer::None);
+}
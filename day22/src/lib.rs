@@ -1,17 +1,13 @@
+use aoc_common::{blocks, Answer, Dir4, Generate, Grid, ParseError, Point, Rng, Solution};
 use nom::{
     branch::alt,
     character::complete::{digit1, one_of},
-    combinator::map,
+    combinator::{map, map_res},
     multi::many1,
     IResult,
 };
-use std::collections::HashMap;
+use tracing::trace;
 
-#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
-struct Point {
-    x: u32,
-    y: u32,
-}
 #[derive(Debug, Eq, PartialEq)]
 enum Step {
     Move(u32),
@@ -20,33 +16,28 @@ enum Step {
 }
 
 #[derive(Debug, Copy, Clone)]
-enum Facing {
-    Right,
-    Left,
-    Down,
-    Up,
+struct Position {
+    pt: Point<usize>,
+    dir: Dir4,
 }
 
-#[derive(Debug, Copy, Clone)]
-struct Position {
-    pt: Point,
-    dir: Facing,
+// The spaces around the map are off it, and only there to be wrapped over
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Tile {
+    Off,
+    Open,
+    Wall,
 }
 
 #[derive(Debug)]
 pub struct Puzzle {
-    rows: u32,
-    largest_x: u32,
-    map: HashMap<Point, char>,
+    map: Grid<Tile>,
     steps: Vec<Step>,
 }
 
 fn parse_direction(input: &str) -> IResult<&str, Step> {
     alt((
-        map(digit1, |s: &str| {
-            let num = s.parse::<u32>().unwrap();
-            Step::Move(num)
-        }),
+        map(map_res(digit1, str::parse), Step::Move),
         map(one_of("LR"), |c| match c {
             'L' => Step::Left,
             'R' => Step::Right,
@@ -56,117 +47,101 @@ fn parse_direction(input: &str) -> IResult<&str, Step> {
 }
 impl Position {
     fn turn_left(&mut self) {
-        self.dir = match self.dir {
-            Facing::Left => Facing::Down,
-            Facing::Down => Facing::Right,
-            Facing::Right => Facing::Up,
-            Facing::Up => Facing::Left,
-        }
+        self.dir = self.dir.turn_left();
     }
     fn turn_right(&mut self) {
-        self.dir = match self.dir {
-            Facing::Right => Facing::Down,
-            Facing::Down => Facing::Left,
-            Facing::Left => Facing::Up,
-            Facing::Up => Facing::Right,
-        }
+        self.dir = self.dir.turn_right();
     }
-    fn score(&self) -> u32 {
+    fn score(&self) -> usize {
         (1000 * (self.pt.y + 1))
             + (4 * (self.pt.x + 1))
             + match self.dir {
-                Facing::Right => 0,
-                Facing::Down => 1,
-                Facing::Left => 2,
-                Facing::Up => 3,
+                Dir4::Right => 0,
+                Dir4::Down => 1,
+                Dir4::Left => 2,
+                Dir4::Up => 3,
             }
     }
 }
-impl Puzzle {
-    pub fn new() -> Self {
-        Self {
-            map: HashMap::new(),
-            rows: 0,
-            steps: Vec::new(),
-            largest_x: 0,
-        }
-    }
-    pub fn add_map_line(&mut self, line: &str) {
-        for (i, c) in line.chars().enumerate() {
-            match c {
-                '.' | '#' => {
-                    self.map.insert(
-                        Point {
-                            x: i as u32,
-                            y: self.rows as u32,
-                        },
-                        c,
-                    );
-                    self.largest_x = self.largest_x.max(i as u32);
+
+// Lines of the map stop after their last tile, so shorter ones are padded
+// out with tiles off the map
+fn parse_map(map: &str) -> Result<Grid<Tile>, ParseError> {
+    let lines = map.lines().map(|line| line.trim_end_matches(' '));
+    let width = lines
+        .clone()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+    let mut grid = Grid::new(width, 0, Tile::Off);
+    for line in lines {
+        let mut row = Vec::with_capacity(width);
+        for (i, c) in line.char_indices() {
+            row.push(match c {
+                '.' => Tile::Open,
+                '#' => Tile::Wall,
+                ' ' => Tile::Off,
+                _ => {
+                    return Err(ParseError::at(
+                        map,
+                        &line[i..],
+                        "an open tile, a wall or a space",
+                    ));
                 }
-                ' ' => {}
-                _ => unreachable!(),
-            };
-        }
-        self.rows += 1;
-    }
-    pub fn add_directions(&mut self, line: &str) {
-        let result = many1(parse_direction)(line).unwrap();
-        for step in result.1 {
-            self.steps.push(step);
+            });
         }
+        row.resize(width, Tile::Off);
+        grid.push_row(row);
     }
-    pub fn add_line(&mut self, line: &str) {
-        if line == "" {
-            return;
-        }
-        match &line[..1] {
-            "." | "#" | " " => self.add_map_line(line),
-            _ => self.add_directions(line),
-        }
-    }
-    fn find_upper_left(&self) -> Point {
-        let mut x = 0;
-        loop {
-            let pt = Point { x, y: 0 };
-            match self.map.get(&pt) {
-                Some(ch) => {
-                    if *ch == '.' {
-                        return pt;
-                    }
-                }
-                None => {}
+    Ok(grid)
+}
+
+impl Puzzle {
+    pub fn add_directions(&mut self, line: &str) -> Result<(), ParseError> {
+        let expected = "a number of tiles, L or R";
+        let steps = match many1(parse_direction)(line) {
+            Ok(("", steps)) => steps,
+            Ok((rest, _)) => return Err(ParseError::at(line, rest, expected)),
+            Err(nom::Err::Error(err) | nom::Err::Failure(err)) => {
+                return Err(ParseError::at(line, err.input, expected));
             }
-            x += 1;
-        }
+            Err(nom::Err::Incomplete(_)) => return Err(ParseError::at_end(line, expected)),
+        };
+        self.steps.extend(steps);
+        Ok(())
+    }
+    fn find_upper_left(&self) -> Point<usize> {
+        self.map
+            .position(|&tile| tile == Tile::Open)
+            .expect("the first row has an open tile")
     }
     fn move_one_2d(&self, pos: &Position) -> Position {
         match pos.dir {
-            Facing::Right => Position {
+            Dir4::Right => Position {
                 pt: Point {
-                    x: (pos.pt.x + 1) % self.largest_x,
+                    x: (pos.pt.x + 1) % self.map.width(),
                     y: pos.pt.y,
                 },
                 dir: pos.dir,
             },
-            Facing::Down => Position {
+            Dir4::Down => Position {
                 pt: Point {
                     x: pos.pt.x,
-                    y: (pos.pt.y + 1) % self.rows,
+                    y: (pos.pt.y + 1) % self.map.height(),
                 },
                 dir: pos.dir,
             },
-            Facing::Left => Position {
+            Dir4::Left => Position {
                 pt: Point {
-                    x: pos.pt.x.checked_sub(1).unwrap_or(self.largest_x - 1),
+                    x: pos.pt.x.checked_sub(1).unwrap_or(self.map.width() - 1),
                     y: pos.pt.y,
                 },
                 dir: pos.dir,
             },
-            Facing::Up => Position {
+            Dir4::Up => Position {
                 pt: Point {
                     x: pos.pt.x,
-                    y: pos.pt.y.checked_sub(1).unwrap_or(self.rows - 1),
+                    y: pos.pt.y.checked_sub(1).unwrap_or(self.map.height() - 1),
                 },
                 dir: pos.dir,
             },
@@ -184,183 +159,246 @@ impl Puzzle {
         let x = pos.pt.x;
         let y = pos.pt.y;
 
-        //println!("pos: {:?}", pos);
         match pos.dir {
-            Facing::Right => match (x, y) {
+            Dir4::Right => match (x, y) {
                 (99, 0..=49) => Position {
                     pt: Point { x: 100, y },
-                    dir: Facing::Right,
+                    dir: Dir4::Right,
                 },
                 (149, 0..=49) => Position {
                     pt: Point { x: 99, y: 149 - y },
-                    dir: Facing::Left,
+                    dir: Dir4::Left,
                 },
                 (99, 50..=99) => Position {
                     pt: Point { x: 50 + y, y: 49 },
-                    dir: Facing::Up,
+                    dir: Dir4::Up,
                 },
                 (49, 100..=149) => Position {
                     pt: Point { x: 50, y },
-                    dir: Facing::Right,
+                    dir: Dir4::Right,
                 },
                 (99, 100..=149) => Position {
                     pt: Point { x: 149, y: 149 - y },
-                    dir: Facing::Left,
+                    dir: Dir4::Left,
                 },
                 (49, 150..=199) => Position {
                     pt: Point { x: y - 100, y: 149 },
-                    dir: Facing::Up,
+                    dir: Dir4::Up,
                 },
                 _ => Position {
                     pt: Point { x: x + 1, y },
-                    dir: Facing::Right,
+                    dir: Dir4::Right,
                 },
             },
-            Facing::Left => match (x, y) {
+            Dir4::Left => match (x, y) {
                 (50, 0..=49) => Position {
                     pt: Point { x: 0, y: 149 - y },
-                    dir: Facing::Right,
+                    dir: Dir4::Right,
                 },
                 (100, 0..=49) => Position {
                     pt: Point { x: 99, y },
-                    dir: Facing::Left,
+                    dir: Dir4::Left,
                 },
                 (50, 50..=99) => Position {
                     pt: Point { x: y - 50, y: 100 },
-                    dir: Facing::Down,
+                    dir: Dir4::Down,
                 },
                 (0, 100..=149) => Position {
                     pt: Point { x: 50, y: 149 - y },
-                    dir: Facing::Right,
+                    dir: Dir4::Right,
                 },
                 (50, 100..=149) => Position {
                     pt: Point { x: 49, y },
-                    dir: Facing::Left,
+                    dir: Dir4::Left,
                 },
                 (0, 150..=199) => Position {
                     pt: Point { x: y - 100, y: 0 },
-                    dir: Facing::Down,
+                    dir: Dir4::Down,
                 },
                 _ => Position {
                     pt: Point { x: x - 1, y },
-                    dir: Facing::Left,
+                    dir: Dir4::Left,
                 },
             },
-            Facing::Up => match (x, y) {
+            Dir4::Up => match (x, y) {
                 (50..=99, 0) => Position {
                     pt: Point { x: 0, y: 100 + x },
-                    dir: Facing::Right,
+                    dir: Dir4::Right,
                 },
                 (100..=149, 0) => Position {
                     pt: Point { x: x - 100, y: 199 },
-                    dir: Facing::Up,
+                    dir: Dir4::Up,
                 },
                 (50..=99, 50) => Position {
                     pt: Point { x, y: 49 },
-                    dir: Facing::Up,
+                    dir: Dir4::Up,
                 },
                 (0..=49, 100) => Position {
                     pt: Point { x: 50, y: x + 50 },
-                    dir: Facing::Right,
+                    dir: Dir4::Right,
                 },
                 (50..=99, 100) => Position {
                     pt: Point { x, y: 99 },
-                    dir: Facing::Up,
+                    dir: Dir4::Up,
                 },
                 (0..=49, 150) => Position {
                     pt: Point { x, y: 149 },
-                    dir: Facing::Up,
+                    dir: Dir4::Up,
                 },
                 _ => Position {
                     pt: Point { x, y: y - 1 },
-                    dir: Facing::Up,
+                    dir: Dir4::Up,
                 },
             },
-            Facing::Down => match (x, y) {
+            Dir4::Down => match (x, y) {
                 (50..=99, 49) => Position {
                     pt: Point { x, y: 50 },
-                    dir: Facing::Down,
+                    dir: Dir4::Down,
                 },
                 (100..=149, 49) => Position {
                     pt: Point { x: 99, y: x - 50 },
-                    dir: Facing::Left,
+                    dir: Dir4::Left,
                 },
                 (50..=99, 99) => Position {
                     pt: Point { x, y: 100 },
-                    dir: Facing::Down,
+                    dir: Dir4::Down,
                 },
                 (0..=49, 149) => Position {
                     pt: Point { x, y: 150 },
-                    dir: Facing::Down,
+                    dir: Dir4::Down,
                 },
                 (50..=99, 149) => Position {
                     pt: Point { x: 49, y: 100 + x },
-                    dir: Facing::Left,
+                    dir: Dir4::Left,
                 },
                 (0..=49, 199) => Position {
                     pt: Point { x: x + 100, y: 0 },
-                    dir: Facing::Down,
+                    dir: Dir4::Down,
                 },
                 _ => Position {
                     pt: Point { x, y: y + 1 },
-                    dir: Facing::Down,
+                    dir: Dir4::Down,
                 },
             },
         }
     }
 
+    // Whether the map is the net `move_cube` folds: the six faces of
+    // `FACES` all on the map and nothing else
+    fn is_folding_net(&self) -> bool {
+        self.map.width() == 150
+            && self.map.height() == 200
+            && self.map.iter().all(|(pt, &tile)| {
+                let on_face = FACES.contains(&((pt.x / 50) as u32, (pt.y / 50) as u32));
+                on_face == (tile != Tile::Off)
+            })
+    }
+
     fn move_self(&self, pos: &Position, move_one: fn(&Self, &Position) -> Position) -> Position {
         let mut new_position = *pos;
         new_position = move_one(self, &new_position);
-        println!("{:?} -> {:?}", pos, new_position);
+        trace!(from = ?pos, to = ?new_position, "move");
 
         loop {
-            match self.map.get(&new_position.pt) {
-                None => {
-                    unreachable!("Huh?");
-                    new_position = move_one(self, &new_position)
-                }
-                Some(ch) => {
-                    if *ch == '#' {
-                        return *pos;
-                    } else {
-                        println!("Returning {:?} -> {:?}", pos, new_position);
-                        return new_position;
-                    }
+            match self.map.get(new_position.pt) {
+                // Off the edge of the map, keep going until we wrap back on
+                None | Some(Tile::Off) => new_position = move_one(self, &new_position),
+                Some(Tile::Wall) => return *pos,
+                Some(Tile::Open) => {
+                    trace!(from = ?pos, to = ?new_position, "wrapped");
+                    return new_position;
                 }
             }
         }
     }
 
-    fn solve(&self, move_one: fn(&Self, &Position) -> Position) -> u32 {
+    fn solve(&self, move_one: fn(&Self, &Position) -> Position) -> usize {
         let mut pos = Position {
             pt: self.find_upper_left(),
-            dir: Facing::Right,
+            dir: Dir4::Right,
         };
 
         for step in self.steps.iter() {
-            println!("Step: {:?} Pos: {:?}", step, pos);
+            trace!(?step, ?pos, "step");
             match step {
                 Step::Left => pos.turn_left(),
                 Step::Right => pos.turn_right(),
                 Step::Move(amount) => {
                     for i in 0..*amount {
-                        println!("-{} {:?}", i, pos);
                         pos = self.move_self(&pos, move_one);
-                        println!("--{} {:?}", i, pos);
+                        trace!(i, ?pos, "moved");
                     }
                 }
             }
         }
         pos.score()
     }
+}
 
-    pub fn pt_1(&self) -> u32 {
-        0
-        // self.solve(Self::move_one_2d)
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let [map, path] = blocks(input)[..] else {
+            return Err(ParseError::at_end(
+                input,
+                "a map and a path separated by a blank line",
+            ));
+        };
+        if !map.lines().next().unwrap().contains('.') {
+            return Err(ParseError::at(input, map, "an open tile to start from"));
+        }
+        let mut puzzle = Puzzle {
+            map: parse_map(map).map_err(|err| err.within(input, map))?,
+            steps: Vec::new(),
+        };
+        let path = path.trim();
+        puzzle
+            .add_directions(path)
+            .map_err(|err| err.within(input, path))?;
+        Ok(puzzle)
+    }
+    fn part1(&self) -> Answer {
+        self.solve(Self::move_one_2d).into()
     }
+    fn part2(&self) -> Answer {
+        // The cube folding is hardcoded for the layout of the real input
+        if !self.is_folding_net() {
+            return Answer::None;
+        }
+        self.solve(Self::move_cube).into()
+    }
+}
 
-    pub fn pt_2(&self) -> u32 {
-        self.solve(Self::move_cube)
+// Where the real inputs put the six faces of the cube, each 50 tiles a
+// side. Only this net can be folded.
+const FACES: [(u32, u32); 6] = [(1, 0), (2, 0), (1, 1), (0, 2), (1, 2), (0, 3)];
+
+impl Generate for Puzzle {
+    // The real inputs' net of the cube with its walls scattered afresh, and
+    // a path of `size` moves
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for y in 0..200 {
+            let faces: Vec<u32> = FACES
+                .iter()
+                .filter(|face| face.1 == y / 50)
+                .map(|face| face.0)
+                .collect();
+            let (first, last) = (faces[0], faces[faces.len() - 1]);
+            input += &" ".repeat(first as usize * 50);
+            for x in first * 50..(last + 1) * 50 {
+                // Keep the start open
+                let wall = (x, y) != (50, 0) && rng.chance(10);
+                input.push(if wall { '#' } else { '.' });
+            }
+            input.push('\n');
+        }
+        input.push('\n');
+        for step in 0..size.max(1) {
+            if step > 0 {
+                input.push(*rng.pick(&['L', 'R']));
+            }
+            input += &rng.range(1..51).to_string();
+        }
+        input + "\n"
     }
 }
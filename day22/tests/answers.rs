@@ -0,0 +1,44 @@
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day22::Puzzle;
+
+// The cube folding for part 2 only knows the layout of the real input
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 6032, Answer::None);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 133_174, 15_410);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(50, 3);
+}
+
+#[test]
+fn maps_that_are_not_the_net() {
+    // As wide as the real input's net, but a single row
+    let puzzle = Puzzle::parse(&format!("{}\n\n10L5\n", ".".repeat(150))).unwrap();
+    assert_eq!(puzzle.part2(), Answer::None);
+
+    // The real input's net with a tile stuck on beside the bottom face
+    let net = |extra: bool| {
+        let mut map = String::new();
+        for y in 0..200 {
+            let row = match y / 50 {
+                0 => format!("{}{}", " ".repeat(50), ".".repeat(100)),
+                1 => format!("{}{}", " ".repeat(50), ".".repeat(50)),
+                2 => ".".repeat(100),
+                _ if extra && y == 199 => ".".repeat(51),
+                _ => ".".repeat(50),
+            };
+            map += &row;
+            map.push('\n');
+        }
+        map + "\n10L5\n"
+    };
+    assert_ne!(Puzzle::parse(&net(false)).unwrap().part2(), Answer::None);
+    assert_eq!(Puzzle::parse(&net(true)).unwrap().part2(), Answer::None);
+}
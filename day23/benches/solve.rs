@@ -0,0 +1,16 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day23::Puzzle;
+
+fn solve(c: &mut Criterion) {
+    bench::<Puzzle>(c, "day23", &["sample.txt", "input.txt"]);
+}
+
+// Part 2 moves the elves round after round until none has to, over a
+// second on the real input, so take fewer samples
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solve
+}
+criterion_main!(benches);
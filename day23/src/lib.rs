@@ -1,174 +1,79 @@
-use std::collections::{HashMap, HashSet, VecDeque};
+use aoc_common::{
+    parse_lines, Animate, Answer, Colour, Dir4, Dir8, Draw, Frame, Generate, Image, ParseError,
+    Point, Rng, Solution, SparseGrid,
+};
+use std::collections::{HashMap, VecDeque};
+use tracing::debug;
 
-#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
-#[derive(Debug)]
-enum Dir {
-    N,
-    S,
-    W,
-    E,
+#[derive(Debug, Clone)]
+pub struct Puzzle {
+    // Where the elves stand
+    elves: SparseGrid<()>,
+    line_num: i64,
+    order: VecDeque<Dir4>,
 }
 
-#[derive(Debug)]
-pub struct Puzzle {
-    elves: HashSet<Point>,
-    line_num: i32,
-    order: VecDeque<Dir>,
+impl Default for Puzzle {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Puzzle {
     pub fn new() -> Self {
-        let mut order: VecDeque<Dir> = VecDeque::new();
-        order.push_back(Dir::N);
-        order.push_back(Dir::S);
-        order.push_back(Dir::W);
-        order.push_back(Dir::E);
+        let order = VecDeque::from([Dir4::Up, Dir4::Down, Dir4::Left, Dir4::Right]);
 
         Self {
-            elves: HashSet::new(),
+            elves: SparseGrid::new(),
             line_num: 0,
             order,
         }
     }
-    pub fn add_line(&mut self, line: &str) {
-        for (i, c) in line.chars().enumerate() {
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
+        for (i, c) in line.char_indices() {
             match c {
                 '#' => {
-                    self.elves.insert(Point {
-                        x: i as i32,
-                        y: self.line_num,
-                    });
+                    self.elves.insert(Point::new(i as i64, self.line_num), ());
                 }
                 '.' => {}
-                _ => unreachable!(),
+                _ => return Err(ParseError::at(line, &line[i..], "an elf # or ground .")),
             };
         }
         self.line_num += 1;
+        Ok(())
     }
-    fn propose_move(&self, elf: &Point, dir: &Dir) -> bool {
-        match dir {
-            Dir::N => {
-                !self.elves.contains(&Point {
-                    x: elf.x - 1,
-                    y: elf.y - 1,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x,
-                    y: elf.y - 1,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x + 1,
-                    y: elf.y - 1,
-                })
-            }
-            Dir::W => {
-                !self.elves.contains(&Point {
-                    x: elf.x - 1,
-                    y: elf.y - 1,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x - 1,
-                    y: elf.y,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x - 1,
-                    y: elf.y + 1,
-                })
-            }
-            Dir::S => {
-                !self.elves.contains(&Point {
-                    x: elf.x - 1,
-                    y: elf.y + 1,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x,
-                    y: elf.y + 1,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x + 1,
-                    y: elf.y + 1,
-                })
-            }
-            Dir::E => {
-                !self.elves.contains(&Point {
-                    x: elf.x + 1,
-                    y: elf.y - 1,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x + 1,
-                    y: elf.y,
-                }) && !self.elves.contains(&Point {
-                    x: elf.x + 1,
-                    y: elf.y + 1,
-                })
-            }
-        }
+    // Free to move if the step and the two diagonals beside it are empty
+    fn propose_move(&self, elf: Point<i64>, dir: Dir4) -> bool {
+        let dir = Dir8::from(dir);
+        [dir.turn_left(), dir, dir.turn_right()]
+            .iter()
+            .all(|look| !self.elves.contains(elf + look.offset()))
     }
-    fn empty_around(&self, elf: &Point) -> bool {
-        for diff_y in -1..=1 {
-            for diff_x in -1..=1 {
-                if !(diff_x == 0 && diff_y == 0)
-                    && self.elves.contains(&Point {
-                        x: elf.x + diff_x,
-                        y: elf.y + diff_y,
-                    })
-                {
-                    return false;
-                }
-            }
-        }
-        true
+    fn empty_around(&self, elf: Point<i64>) -> bool {
+        elf.neighbours8().iter().all(|&pt| !self.elves.contains(pt))
     }
 
     fn round(&mut self) -> bool {
-        let mut desired_move: HashMap<Point, Vec<Point>> = HashMap::new();
+        let mut desired_move: HashMap<Point<i64>, Vec<Point<i64>>> = HashMap::new();
         let mut moved = false;
 
-        for elf in self.elves.iter() {
+        for elf in self.elves.positions() {
             if self.empty_around(elf) {
                 continue;
             }
-            for dir in self.order.iter() {
-                if self.propose_move(elf, dir) {
-                    match dir {
-                        Dir::N => desired_move
-                            .entry(Point {
-                                x: elf.x,
-                                y: elf.y - 1,
-                            })
-                            .or_default()
-                            .push(*elf),
-                        Dir::E => desired_move
-                            .entry(Point {
-                                x: elf.x + 1,
-                                y: elf.y,
-                            })
-                            .or_default()
-                            .push(*elf),
-                        Dir::S => desired_move
-                            .entry(Point {
-                                x: elf.x,
-                                y: elf.y + 1,
-                            })
-                            .or_default()
-                            .push(*elf),
-                        Dir::W => desired_move
-                            .entry(Point {
-                                x: elf.x - 1,
-                                y: elf.y,
-                            })
-                            .or_default()
-                            .push(*elf),
-                    }
-                    break;
-                } else {
-                    //println!("-No move...");
-                }
+            if let Some(dir) = self.order.iter().find(|&&dir| self.propose_move(elf, dir)) {
+                desired_move
+                    .entry(elf + dir.offset())
+                    .or_default()
+                    .push(elf);
             }
         }
         // make moves
         for (space, list_elves) in desired_move.iter() {
             if list_elves.len() == 1 {
                 moved = true;
-                self.elves.remove(&list_elves[0]);
-                self.elves.insert(*space);
+                self.elves.remove(list_elves[0]);
+                self.elves.insert(*space, ());
             }
         }
         // change order of looking
@@ -176,72 +81,99 @@ impl Puzzle {
         moved
     }
 
-    fn score(&self) -> i32 {
-        let mut largest_x = i32::MIN;
-        let mut smallest_x = i32::MAX;
-        let mut largest_y = i32::MIN;
-        let mut smallest_y = i32::MAX;
-        for elf in self.elves.iter() {
-            largest_x = largest_x.max(elf.x);
-            smallest_x = smallest_x.min(elf.x);
-            largest_y = largest_y.max(elf.y);
-            smallest_y = smallest_y.min(elf.y);
+    // Empty ground in the smallest rectangle holding every elf
+    fn score(&self) -> i64 {
+        match self.elves.bounds() {
+            Some((min, max)) => (max.x - min.x + 1) * (max.y - min.y + 1) - self.elves.len() as i64,
+            None => 0,
         }
-        let mut answer = 0;
-        for x in smallest_x..=largest_x {
-            for y in smallest_y..=largest_y {
-                if !self.elves.contains(&Point { x, y }) {
-                    answer += 1;
-                }
+    }
+    fn frame(&self, caption: impl Into<String>) -> Frame {
+        let bounds = self.elves.bounds().unwrap_or_default();
+        Frame::from_sparse(&self.elves, bounds, caption, |elf| match elf {
+            Some(()) => ('#', Colour::Green),
+            None => ('.', Colour::Grey),
+        })
+    }
+}
+
+impl Animate for Puzzle {
+    // The grove before the first round and after each one, for ten rounds in
+    // part 1 and until the elves stop in part 2
+    fn frames(&self, part: u8) -> Box<dyn Iterator<Item = Frame> + '_> {
+        let mut puzzle = self.clone();
+        let mut round = 0;
+        let mut moved = true;
+        Box::new(std::iter::from_fn(move || {
+            let done = if part == 1 { round > 10 } else { !moved };
+            if done {
+                return None;
             }
-        }
-        answer
+            if round > 0 {
+                moved = puzzle.round();
+            }
+            round += 1;
+            Some(puzzle.frame(format!("Day 23 part {}: round {}", part, round - 1)))
+        }))
     }
-    fn display(&self) {
-        let mut largest_x = 10;
-        let mut smallest_x = -3;
-        let mut largest_y = 9;
-        let mut smallest_y = -2;
-        for elf in self.elves.iter() {
-            largest_x = largest_x.max(elf.x);
-            smallest_x = smallest_x.min(elf.x);
-            largest_y = largest_y.max(elf.y);
-            smallest_y = smallest_y.min(elf.y);
-        }
-        for y in smallest_y..=largest_y {
-            let mut line: String = "".to_string();
-            for x in smallest_x..=largest_x {
-                if self.elves.contains(&Point { x, y }) {
-                    line.push('#');
-                } else {
-                    line.push('.');
-                }
+}
+
+impl Draw for Puzzle {
+    // Where the elves stand after ten rounds in part 1, or once they've
+    // stopped in part 2
+    fn image(&self, part: u8) -> Image {
+        let mut puzzle = self.clone();
+        if part == 1 {
+            for _ in 0..10 {
+                puzzle.round();
             }
-            println!("{}", line);
+        } else {
+            while puzzle.round() {}
         }
+        Image::from_frame(&puzzle.frame(""))
     }
-    pub fn pt_1(&mut self) -> i32 {
-        //self.display();
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut puzzle = Puzzle::new();
+        parse_lines(input, |line| puzzle.add_line(line))?;
+        Ok(puzzle)
+    }
+    fn part1(&self) -> Answer {
+        let mut puzzle = self.clone();
 
         for round in 0..10 {
-            let moved = self.round();
-            println!("Round {}", round + 1);
-            //self.display();
+            puzzle.round();
+            debug!(round = round + 1, "round done");
         }
-        self.score()
+        puzzle.score().into()
     }
-    pub fn pt_2(&mut self) -> i32 {
-        let mut moved = true;
-        let mut round = 10; // Part 1 has already moved 10 times
-                            //self.display();
+    fn part2(&self) -> Answer {
+        let mut puzzle = self.clone();
+        let mut round = 0;
 
+        let mut moved = true;
         while moved {
-            moved = self.round();
+            moved = puzzle.round();
             round += 1;
-            println!("Round {}", round);
-            //self.display();
+            debug!(round, "round done");
         }
-        round
-        //self.score()
+        round.into()
+    }
+}
+
+impl Generate for Puzzle {
+    // About `size` elves scattered over a square twice as big
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let side = ((size.max(1) * 2) as f64).sqrt().ceil() as usize;
+        (0..side)
+            .map(|_| {
+                let row: String = (0..side)
+                    .map(|_| if rng.chance(50) { '#' } else { '.' })
+                    .collect();
+                row + "\n"
+            })
+            .collect()
     }
 }
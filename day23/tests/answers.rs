@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day23::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 110, 20);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 4000, 1040);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(50, 3);
+}
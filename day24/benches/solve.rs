@@ -0,0 +1,16 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day24::Puzzle;
+
+fn solve(c: &mut Criterion) {
+    bench::<Puzzle>(c, "day24", &["sample.txt", "simple.txt", "input.txt"]);
+}
+
+// Crossing the real valley there, back and there again takes over a
+// second, so take fewer samples
+criterion_group! {
+    name = benches;
+    config = Criterion::default().sample_size(10);
+    targets = solve
+}
+criterion_main!(benches);
@@ -1,42 +1,65 @@
-use std::collections::{BTreeMap, HashMap};
+use aoc_common::{
+    bfs, parse_lines, Animate, Answer, Colour, Dir4, Frame, Generate, Grid, ParseError, Path, Rng,
+    Solution,
+};
+use std::collections::BTreeMap;
+use tracing::debug;
 
-#[derive(Debug, Eq, Hash, PartialEq, Copy, Clone, Ord, PartialOrd)]
-struct Point {
-    x: usize,
-    y: usize,
-}
+type Point = aoc_common::Point<usize>;
+type BlizzardMap = BTreeMap<Point, Vec<Dir4>>;
 
-#[derive(Debug, Copy, Clone)]
-enum Blizzard {
-    Up,
-    Down,
-    Left,
-    Right,
+fn blizzard_dir(c: char) -> Dir4 {
+    match c {
+        '^' => Dir4::Up,
+        '<' => Dir4::Left,
+        '>' => Dir4::Right,
+        'v' => Dir4::Down,
+        _ => unreachable!(),
+    }
 }
 
-type BlizzardMap = BTreeMap<Point, Vec<Blizzard>>;
+fn blizzard_char(dir: Dir4) -> char {
+    match dir {
+        Dir4::Up => '^',
+        Dir4::Left => '<',
+        Dir4::Right => '>',
+        Dir4::Down => 'v',
+    }
+}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Puzzle {
     blizzards: BlizzardMap,
-    cache_blizzards: HashMap<u32, BlizzardMap>,
-    cache_steps_left: HashMap<(u32, Point), u32>,
     line_num: usize,
     start_pt: Point,
     end_pt: Point,
     width: usize,
-    best_time: u32,
 }
 
-impl Blizzard {
-    fn from_char(c: char) -> Self {
-        match c {
-            '^' => Blizzard::Up,
-            '<' => Blizzard::Left,
-            '>' => Blizzard::Right,
-            'v' => Blizzard::Down,
-            _ => unreachable!(),
-        }
+// Where the blizzards are at each minute of their cycle. They wrap round
+// the valley, so they're back where they started after a number of
+// minutes that both its width and height divide.
+struct Forecast {
+    occupied: Vec<Grid<bool>>,
+}
+
+impl Forecast {
+    fn blocked(&self, minute: usize, pt: Point) -> bool {
+        self.occupied[minute % self.occupied.len()][pt]
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -48,17 +71,20 @@ impl Puzzle {
             start_pt: Point { x: 0, y: 0 },
             end_pt: Point { x: 0, y: 0 },
             width: 0,
-            cache_blizzards: HashMap::new(),
-            cache_steps_left: HashMap::new(),
-            best_time: u32::MAX,
         }
     }
 
-    pub fn add_line(&mut self, line: &str) {
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
         if self.line_num == 0 {
+            if line.len() < 3 {
+                return Err(ParseError::at(line, line, "a valley between two walls"));
+            }
             self.width = line.len() - 2;
+        } else if line.len() != self.width + 2 {
+            let expected = format!("a row {} wide", self.width + 2);
+            return Err(ParseError::at(line, line, expected));
         }
-        for (i, c) in line.chars().enumerate() {
+        for (i, c) in line.char_indices() {
             match c {
                 '.' => {
                     if self.line_num == 0 {
@@ -69,6 +95,9 @@ impl Puzzle {
                     }
                 }
                 '#' => {}
+                '^' | '<' | '>' | 'v' if self.line_num == 0 || i == 0 || i > self.width => {
+                    return Err(ParseError::at(line, &line[i..], "ground . or a wall #"));
+                }
                 '^' | '<' | '>' | 'v' => self
                     .blizzards
                     .entry(Point {
@@ -76,34 +105,41 @@ impl Puzzle {
                         y: self.line_num,
                     })
                     .or_default()
-                    .push(Blizzard::from_char(c)),
-                _ => unreachable!(),
+                    .push(blizzard_dir(c)),
+                _ => {
+                    return Err(ParseError::at(
+                        line,
+                        &line[i..],
+                        "ground ., a wall # or a blizzard",
+                    ));
+                }
             };
         }
         self.line_num += 1;
+        Ok(())
     }
-    fn move_blizzard(&self, blizzard: &Blizzard, pt: &Point) -> Point {
+    fn move_blizzard(&self, blizzard: &Dir4, pt: &Point) -> Point {
         let mut new_pt = *pt;
         match blizzard {
-            Blizzard::Up => {
+            Dir4::Up => {
                 new_pt.y -= 1;
                 if new_pt.y == 0 {
                     new_pt.y = self.line_num - 2;
                 }
             }
-            Blizzard::Down => {
+            Dir4::Down => {
                 new_pt.y += 1;
                 if new_pt.y == self.line_num - 1 {
                     new_pt.y = 1;
                 }
             }
-            Blizzard::Left => {
+            Dir4::Left => {
                 new_pt.x -= 1;
                 if new_pt.x == 0 {
                     new_pt.x = self.width;
                 }
             }
-            Blizzard::Right => {
+            Dir4::Right => {
                 new_pt.x += 1;
                 if new_pt.x > self.width {
                     new_pt.x = 1;
@@ -117,107 +153,198 @@ impl Puzzle {
 
         for (pt, list_blizzard) in blizzards.iter() {
             for blizzard in list_blizzard {
-                let new_pt = self.move_blizzard(blizzard, &pt);
+                let new_pt = self.move_blizzard(blizzard, pt);
                 new_blizzards.entry(new_pt).or_default().push(*blizzard);
             }
         }
         new_blizzards
     }
 
-    fn can_move(&self, blizzards: &BlizzardMap, new_pt: Point) -> bool {
-        if new_pt == self.end_pt {
+    fn forecast(&self) -> Forecast {
+        let height = self.line_num - 2;
+        let period = (self.width * height / gcd(self.width, height)).max(1);
+        let mut blizzards = self.blizzards.clone();
+        let mut occupied = Vec::with_capacity(period);
+        for _ in 0..period {
+            let mut grid = Grid::new(self.width + 2, self.line_num, false);
+            for pt in blizzards.keys() {
+                grid[*pt] = true;
+            }
+            occupied.push(grid);
+            blizzards = self.move_blizzards(&blizzards);
+        }
+        Forecast { occupied }
+    }
+
+    fn can_move(&self, forecast: &Forecast, minute: usize, new_pt: Point, to: Point) -> bool {
+        if new_pt == to {
             return true;
         }
         if new_pt.x == 0 || new_pt.x > self.width {
             return false;
         }
-        if new_pt.y == 0 || new_pt.y >= self.end_pt.y.max(self.start_pt.y) {
+        if new_pt.y == 0 || new_pt.y >= self.line_num - 1 {
             return false;
         }
-        match blizzards.get(&new_pt) {
-            Some(_) => false,
-            None => true,
+        !forecast.blocked(minute, new_pt)
+    }
+
+    // The quickest way across the valley from `from` to `to`, setting off
+    // at minute `start`. The blizzards repeat, so where we are and the
+    // minute within their cycle is all the search needs to tell states apart.
+    fn cross(
+        &self,
+        forecast: &Forecast,
+        start: usize,
+        from: Point,
+        to: Point,
+    ) -> Option<Path<(usize, Point), usize>> {
+        let period = forecast.occupied.len();
+        let path = bfs(
+            [(start % period, from)],
+            |&(minute, curr_pt)| {
+                let next_min = (minute + 1) % period;
+                let mut attempts: Vec<(usize, Point)> = Dir4::ALL
+                    .into_iter()
+                    .filter_map(|dir| curr_pt.step(dir))
+                    .filter(|&new_pt| self.can_move(forecast, next_min, new_pt, to))
+                    .map(|new_pt| (next_min, new_pt))
+                    .collect();
+                if curr_pt == from || self.can_move(forecast, next_min, curr_pt, to) {
+                    attempts.push((next_min, curr_pt));
+                }
+                attempts
+            },
+            |&(_, pt)| pt == to,
+        )?;
+        debug!(minutes = path.cost, "crossed");
+        Some(path)
+    }
+
+    // Where the expedition is each minute: crossing once in part 1, and
+    // there, back and there again in part 2
+    fn route(&self, part: u8) -> Option<Vec<Point>> {
+        let forecast = self.forecast();
+        let (start, end) = (self.start_pt, self.end_pt);
+        let trips = if part == 1 {
+            vec![(start, end)]
+        } else {
+            vec![(start, end), (end, start), (start, end)]
+        };
+        let mut route = vec![start];
+        for (from, to) in trips {
+            let path = self.cross(&forecast, route.len() - 1, from, to)?;
+            route.extend(path.nodes[1..].iter().map(|&(_, pt)| pt));
         }
+        Some(route)
     }
 
-    fn solve(&mut self, min: u32, curr_pt: Point) -> u32 {
-        let mut best = u32::MAX;
-        if let Some(val) = self.cache_steps_left.get(&(min, curr_pt)) {
-            return *val;
+    fn frame(&self, blizzards: &BlizzardMap, expedition: Point, caption: String) -> Frame {
+        let mut frame = Frame::new(caption);
+        for y in 0..self.line_num {
+            frame.push_row((0..self.width + 2).map(|x| {
+                let pt = Point { x, y };
+                let wall = x == 0 || x == self.width + 1 || y == 0 || y == self.line_num - 1;
+                if pt == expedition {
+                    ('E', Colour::Red)
+                } else if pt == self.start_pt || pt == self.end_pt {
+                    ('.', Colour::Grey)
+                } else if wall {
+                    ('#', Colour::Plain)
+                } else {
+                    match blizzards.get(&pt).map(Vec::as_slice) {
+                        None | Some([]) => ('.', Colour::Grey),
+                        Some(&[dir]) => (blizzard_char(dir), Colour::Cyan),
+                        Some(many) => {
+                            let count = char::from_digit(many.len() as u32, 10).unwrap_or('*');
+                            (count, Colour::Blue)
+                        }
+                    }
+                }
+            }));
         }
-        if min > self.best_time {
-            return u32::MAX; // go ahead and give up, this can't be better
+        frame.with_focus(expedition)
+    }
+}
+
+impl Animate for Puzzle {
+    // A frame for each minute of the expedition's route
+    fn frames(&self, part: u8) -> Box<dyn Iterator<Item = Frame> + '_> {
+        let route = self.route(part).unwrap_or_default();
+        let mut blizzards = self.blizzards.clone();
+        Box::new(route.into_iter().enumerate().map(move |(minute, pt)| {
+            let caption = format!("Day 24 part {}: minute {}", part, minute);
+            let frame = self.frame(&blizzards, pt, caption);
+            blizzards = self.move_blizzards(&blizzards);
+            frame
+        }))
+    }
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut puzzle = Puzzle::new();
+        parse_lines(input, |line| puzzle.add_line(line))?;
+        if puzzle.line_num < 3 {
+            return Err(ParseError::at_end(input, "a valley at least 3 rows high"));
         }
-        if curr_pt == self.end_pt {
-            best = 0;
-            self.best_time = min;
-            println!("Found solution: {} {:?}", min, curr_pt);
-        } else {
-            //println!("Solving: {} {:?}", min, curr_pt);
-            let next_min = min + 1;
-            if let None = self.cache_blizzards.get(&next_min) {
-                let curr_blizzard = self.cache_blizzards.get(&min).unwrap();
-                let new_blizzards = self.move_blizzards(curr_blizzard);
-                self.cache_blizzards.insert(next_min, new_blizzards);
-            }
-            let blizzards = match self.cache_blizzards.get(&next_min) {
-                Some(x) => x,
-                None => unreachable!(),
-            };
-            let mut attempts: Vec<Point> = Vec::new();
+        // Blizzards can't be told apart from the inside until the last row
+        // turns out to be the wall
+        let wall = puzzle.line_num - 1;
+        if let Some(pt) = puzzle.blizzards.keys().find(|pt| pt.y == wall) {
+            let line = input.lines().nth(wall).unwrap_or_default();
+            return Err(
+                ParseError::at(line, &line[pt.x..], "ground . or a wall #").within(input, line)
+            );
+        }
+        let first = input.lines().next().unwrap_or_default();
+        if !first.contains('.') {
+            return Err(ParseError::at(
+                input,
+                first,
+                "an entrance . in the top wall",
+            ));
+        }
+        // Any ground below the top is taken for the exit as it's read, so
+        // it's only the exit if it was on the last row
+        if puzzle.end_pt.y != wall {
+            let line = input.lines().nth(wall).unwrap_or_default();
+            return Err(ParseError::at(input, line, "an exit . in the bottom wall"));
+        }
+        Ok(puzzle)
+    }
+    fn part1(&self) -> Answer {
+        self.route(1)
+            .map_or(Answer::None, |route| (route.len() - 1).into())
+    }
+    fn part2(&self) -> Answer {
+        self.route(2)
+            .map_or(Answer::None, |route| (route.len() - 1).into())
+    }
+}
 
-            let range: [(i32, i32); 4] = if self.end_pt.y > curr_pt.y {
-                [(1, 0), (0, 1), (-1, 0), (0, -1)]
-            } else {
-                [(-1, 0), (0, -1), (1, 0), (0, 1)]
-            };
-            for d in &range {
-                let new_pt = Point {
-                    x: (curr_pt.x as i32 + d.0) as usize,
-                    y: (curr_pt.y as i32 + d.1) as usize,
+impl Generate for Puzzle {
+    // A valley `size` wide, at least 3, and a quarter as high, crowded with
+    // blizzards. None blow up or down the entrance's and exit's columns.
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let width = size.max(3);
+        let height = (width / 4).max(2);
+        let mut input = format!("#.{}\n", "#".repeat(width));
+        for _ in 0..height {
+            input.push('#');
+            for x in 1..=width {
+                let blizzards: &[char] = if x == 1 || x == width {
+                    &['<', '>']
+                } else {
+                    &['^', 'v', '<', '>']
                 };
-                if self.can_move(blizzards, new_pt) {
-                    attempts.push(new_pt);
-                }
-            }
-            if curr_pt == self.start_pt || self.can_move(blizzards, curr_pt) {
-                attempts.push(curr_pt);
-            }
-
-            for pt in attempts {
-                let result = self.solve(min + 1, pt);
-                if result != u32::MAX {
-                    best = best.min(result + 1);
-                }
+                input.push(match rng.chance(60) {
+                    true => *rng.pick(blizzards),
+                    false => '.',
+                });
             }
+            input += "#\n";
         }
-        self.cache_steps_left.insert((min, curr_pt), best);
-        best
-    }
-    pub fn pt_1(&mut self) -> u32 {
-        self.cache_blizzards.insert(0, self.blizzards.clone());
-        self.solve(0, self.start_pt)
-    }
-    pub fn pt_2(&mut self) -> u32 {
-        let first_path = self.pt_1();
-        println!("First path: {}", first_path);
-        let tmp_pt = self.start_pt;
-        self.start_pt = self.end_pt;
-        self.end_pt = tmp_pt;
-        self.cache_steps_left.clear();
-        self.best_time = u32::MAX;
-        self.blizzards = self.cache_blizzards.get(&first_path).unwrap().clone();
-        self.cache_blizzards.clear();
-        let path_back = self.pt_1();
-        println!("Path back: {}", path_back);
-        self.end_pt = self.start_pt;
-        self.start_pt = tmp_pt;
-        self.cache_steps_left.clear();
-        self.best_time = u32::MAX;
-        self.blizzards = self.cache_blizzards.get(&path_back).unwrap().clone();
-        self.cache_blizzards.clear();
-        let path_back_again = self.pt_1();
-        println!("Path back AGAIN: {}", path_back_again);
-        first_path + path_back + path_back_again
+        input + &"#".repeat(width) + ".#\n"
     }
 }
@@ -0,0 +1,43 @@
+use aoc_common::{check, check_generated, check_input, Solution};
+use day24::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 18, 54);
+}
+
+#[test]
+fn simple() {
+    check::<Puzzle>("simple.txt", 10, 30);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 230, 713);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(12, 3);
+}
+
+#[test]
+fn rejects_bad_valleys() {
+    assert_eq!(Puzzle::parse("#.#\n").unwrap_err().line, 1);
+    assert!(Puzzle::parse("#.###\n#...#\n").is_err());
+    assert!(Puzzle::parse("").is_err());
+    // Blizzards in the walls
+    let err = Puzzle::parse("#>###\n#...#\n###.#\n").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 2));
+    let err = Puzzle::parse("#.###\n>...#\n###.#\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 1));
+    let err = Puzzle::parse("#.###\n#...<\n###.#\n").unwrap_err();
+    assert_eq!((err.line, err.column), (2, 5));
+    let err = Puzzle::parse("#.###\n#...#\n##v.#\n").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 3));
+    // No way in or out
+    let err = Puzzle::parse("#####\n#...#\n###.#\n").unwrap_err();
+    assert_eq!((err.line, err.column), (1, 1));
+    let err = Puzzle::parse("#.###\n#...#\n#####\n").unwrap_err();
+    assert_eq!((err.line, err.column), (3, 1));
+}
@@ -1,80 +1,132 @@
+use aoc_common::{parse_lines, Answer, Generate, ParseError, Rng, Solution};
+use std::{fmt, str::FromStr};
+
 #[derive(Debug)]
 pub struct Puzzle {
     amount_fuel: Vec<Snafu>,
 }
 
-#[derive(Debug)]
+/// A number written in balanced base 5, with digits 2, 1, 0, - for minus
+/// one and = for minus two.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Snafu {
     val: i64,
 }
 
 impl Snafu {
-    fn new(line: &str) -> Self {
-        let mut val = 0;
-        let mut place = 0;
-        let mut str = line.to_string();
-        loop {
-            match str.pop() {
-                None => return Self { val },
-                Some(ch) => {
-                    val += match ch {
-                        '2' => 2 * 5_i64.pow(place),
-                        '1' => 1 * 5_i64.pow(place),
-                        '0' => 0 * 5_i64.pow(place),
-                        '-' => -1 * 5_i64.pow(place),
-                        '=' => -2 * 5_i64.pow(place),
-                        _ => unreachable!(),
-                    };
-                    place += 1;
-                }
-            }
-        }
+    pub fn value(self) -> i64 {
+        self.val
     }
-    fn from_val(val: i64) -> Self {
+}
+
+impl From<i64> for Snafu {
+    fn from(val: i64) -> Self {
         Self { val }
     }
-    fn get_string(&self) -> String {
-        let mut number = self.val;
-        let mut result = String::new();
+}
+
+impl FromStr for Snafu {
+    type Err = ParseError;
+
+    fn from_str(line: &str) -> Result<Self, ParseError> {
+        if line.is_empty() {
+            return Err(ParseError::at(line, line, "a SNAFU digit 2, 1, 0, - or ="));
+        }
+        let mut val: i64 = 0;
+        for (pos, ch) in line.char_indices() {
+            let digit = match ch {
+                '2' => 2,
+                '1' => 1,
+                '0' => 0,
+                '-' => -1,
+                '=' => -2,
+                _ => {
+                    return Err(ParseError::at(
+                        line,
+                        &line[pos..],
+                        "a SNAFU digit 2, 1, 0, - or =",
+                    ))
+                }
+            };
+            val = val
+                .checked_mul(5)
+                .and_then(|val| val.checked_add(digit))
+                .ok_or_else(|| ParseError::at(line, line, "a number that fits in 64 bits"))?;
+        }
+        Ok(Self { val })
+    }
+}
 
-        while number > 0 {
-            let remainder = number % 5;
-            result.insert(
-                0,
-                match remainder {
-                    0 => '0',
-                    1 => '1',
-                    2 => '2',
-                    3 => '=',
-                    4 => '-',
-                    _ => unreachable!(),
-                },
-            );
-            if remainder >= 3 {
-                number += 5 - remainder
+impl fmt::Display for Snafu {
+    // Zero is written as 0, every other number without leading zeros
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // Wide enough that carrying past i64::MAX or i64::MIN can't overflow
+        let mut number = self.val as i128;
+        let mut digits = Vec::new();
+        loop {
+            let (digit, value) = match number.rem_euclid(5) {
+                0 => ('0', 0),
+                1 => ('1', 1),
+                2 => ('2', 2),
+                3 => ('=', -2),
+                _ => ('-', -1),
+            };
+            digits.push(digit);
+            number = (number - value) / 5;
+            if number == 0 {
+                break;
             }
-            number /= 5;
         }
-        return result;
+        digits
+            .iter()
+            .rev()
+            .try_for_each(|&digit| write!(f, "{}", digit))
     }
 }
+
+impl Default for Puzzle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Puzzle {
     pub fn new() -> Self {
         Self {
             amount_fuel: Vec::new(),
         }
     }
-    pub fn add_line(&mut self, line: &str) {
-        self.amount_fuel.push(Snafu::new(line));
+    pub fn add_line(&mut self, line: &str) -> Result<(), ParseError> {
+        self.amount_fuel.push(line.parse()?);
+        Ok(())
     }
-    pub fn pt_1(&mut self) -> i64 {
-        let mut total = 0;
-        for fuel in self.amount_fuel.iter() {
-            total += fuel.val;
-        }
-        let snafu = Snafu::from_val(total);
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut puzzle = Puzzle::new();
+        parse_lines(input, |line| puzzle.add_line(line))?;
+        Ok(puzzle)
+    }
+    fn part1(&self) -> Answer {
+        // None if the fuel adds up to more than 64 bits hold
+        let total = self
+            .amount_fuel
+            .iter()
+            .try_fold(0i64, |total, fuel| total.checked_add(fuel.val));
+        total.map_or(Answer::None, |total| Snafu::from(total).to_string().into())
+    }
+    // Day 25 only has the one puzzle
+    fn part2(&self) -> Answer {
+        Answer::None
+    }
+}
 
-        println!("Total: {} {:?}", snafu.get_string(), snafu);
-        0
+impl Generate for Puzzle {
+    // `size` fuel requirements
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size.max(1))
+            .map(|_| format!("{}\n", Snafu::from(rng.range(1..1_000_000_000))))
+            .collect()
     }
 }
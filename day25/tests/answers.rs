@@ -0,0 +1,30 @@
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day25::{Puzzle, Snafu};
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", "2=-1=0", Answer::None);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", "2=020-===0-1===2=020", Answer::None);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(50, 3);
+}
+
+#[test]
+fn fuel_too_much_to_add_up() {
+    // Each fits in 64 bits, but not both together
+    let big = Snafu::from(i64::MAX).to_string();
+    let puzzle = Puzzle::parse(&format!("{}\n1\n", big)).unwrap();
+    assert_eq!(puzzle.part1(), Answer::None);
+    let puzzle = Puzzle::parse(&format!("{}\n-\n", big)).unwrap();
+    assert_eq!(
+        puzzle.part1(),
+        Answer::Text(Snafu::from(i64::MAX - 1).to_string())
+    );
+}
@@ -0,0 +1,29 @@
+use day25::Snafu;
+use proptest::prelude::*;
+
+#[test]
+fn zero_and_negatives() {
+    assert_eq!(Snafu::from(0).to_string(), "0");
+    assert_eq!(Snafu::from(-1).to_string(), "-");
+    assert_eq!(Snafu::from(-3).to_string(), "-2");
+    assert_eq!("=".parse::<Snafu>().unwrap().value(), -2);
+    assert!("".parse::<Snafu>().is_err());
+    // Too big for 64 bits
+    assert!("2".repeat(28).parse::<Snafu>().is_err());
+}
+
+proptest! {
+    #[test]
+    fn numbers_round_trip(value in any::<i64>()) {
+        let snafu = Snafu::from(value);
+        prop_assert_eq!(snafu.to_string().parse::<Snafu>().unwrap(), snafu);
+    }
+
+    // Without a leading zero, a string of up to 27 digits is the only way to
+    // write its number
+    #[test]
+    fn digits_round_trip(digits in "[12=-][012=-]{0,26}") {
+        let snafu: Snafu = digits.parse().unwrap();
+        prop_assert_eq!(snafu.to_string(), digits);
+    }
+}
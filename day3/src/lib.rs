@@ -1,3 +1,5 @@
+use aoc_common::{parse_lines, Answer, Generate, ParseError, Rng, Solution};
+
 #[derive(Debug)]
 pub struct Rucksack {
     items_top: String,
@@ -12,21 +14,27 @@ impl Rucksack {
             items_bottom: bottom.to_string(),
         }
     }
-    pub fn find_duplicated(&self) -> char {
-        for ch in self.items_top.chars() {
-            if self.items_bottom.contains(ch) {
-                return ch;
-            }
+    // Items are letters, split evenly between the two compartments
+    fn parse(line: &str) -> Result<Self, ParseError> {
+        if let Some(pos) = line.find(|ch: char| !ch.is_ascii_alphabetic()) {
+            return Err(ParseError::at(line, &line[pos..], "an item a-z or A-Z"));
         }
-        panic!("No duplicates: {} {}", self.items_top, self.items_bottom);
+        if !line.len().is_multiple_of(2) {
+            return Err(ParseError::at_end(line, "an even number of items"));
+        }
+        Ok(Rucksack::new(line.to_string()))
+    }
+    pub fn find_duplicated(&self) -> Option<char> {
+        self.items_top
+            .chars()
+            .find(|&ch| self.items_bottom.contains(ch))
     }
 
     pub fn get_string(&self) -> String {
-        let result = self.items_top.clone() + &self.items_bottom;
-        result
+        self.items_top.clone() + &self.items_bottom
     }
 
-    pub fn find_all_duplicates(&self, str: &String) -> String {
+    pub fn find_all_duplicates(&self, str: &str) -> String {
         let mut matches = String::new();
         for ch in str.chars() {
             if self.items_bottom.contains(ch) {
@@ -47,3 +55,68 @@ impl Rucksack {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct Puzzle {
+    rucksacks: Vec<Rucksack>,
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self {
+            rucksacks: parse_lines(input, Rucksack::parse)?,
+        })
+    }
+    // None if a rucksack has nothing in both compartments
+    fn part1(&self) -> Answer {
+        self.rucksacks
+            .iter()
+            .map(|sack| sack.find_duplicated().map(Rucksack::get_priority))
+            .sum::<Option<i32>>()
+            .map_or(Answer::None, Answer::from)
+    }
+    fn part2(&self) -> Answer {
+        // For each set of 3, find the similarities. None if a group has none
+        self.rucksacks
+            .chunks_exact(3)
+            .map(|group| {
+                let str1 = group[0].get_string();
+                let str2 = group[1].find_all_duplicates(&str1);
+                let result = group[2].find_all_duplicates(&str2);
+                result.chars().next().map(Rucksack::get_priority)
+            })
+            .sum::<Option<i32>>()
+            .map_or(Answer::None, Answer::from)
+    }
+}
+
+impl Generate for Puzzle {
+    // `size` rucksacks, rounded up to whole groups of three. Each group
+    // shares only its badge, each rucksack's compartments only the item
+    // packed twice, so the rest of the items come from pools of their own
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut input = String::new();
+        for _ in 0..size.div_ceil(3).max(1) {
+            let mut items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+            rng.shuffle(&mut items);
+            let badge = items[0];
+            for (elf, pools) in items[4..].chunks(16).enumerate() {
+                let doubled = items[1 + elf];
+                let half = rng.range(4..16) as usize;
+                let fill = |rng: &mut Rng, pool: &[char], half: &mut Vec<char>, len: usize| {
+                    while half.len() < len {
+                        half.push(*rng.pick(pool));
+                    }
+                    rng.shuffle(half);
+                };
+                let mut top = vec![badge, doubled];
+                fill(rng, &pools[..8], &mut top, half);
+                let mut bottom = vec![doubled];
+                fill(rng, &pools[8..], &mut bottom, half);
+                input.extend(top.into_iter().chain(bottom));
+                input.push('\n');
+            }
+        }
+        input
+    }
+}
@@ -0,0 +1,24 @@
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day3::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 157, 70);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 7980, 2881);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(30, 3);
+}
+
+#[test]
+fn nothing_shared() {
+    let puzzle = Puzzle::parse("ab\ncd\nef\n").unwrap();
+    assert_eq!(puzzle.part1(), Answer::None);
+    assert_eq!(puzzle.part2(), Answer::None);
+}
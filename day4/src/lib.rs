@@ -1,3 +1,6 @@
+use aoc_common::{number, parse_lines, Answer, Generate, ParseError, Rng, Solution};
+use regex::Regex;
+
 #[derive(Debug)]
 pub struct CleaningAssignment {
     first: i32,
@@ -5,22 +8,72 @@ pub struct CleaningAssignment {
 }
 
 impl CleaningAssignment {
-    pub fn new(first: &str, second: &str) -> Self {
-        Self {
-            first: first.parse::<i32>().unwrap(),
-            second: second.parse::<i32>().unwrap(),
-        }
+    // The sections from `first` to `second`, both included
+    pub fn new(first: i32, second: i32) -> Self {
+        Self { first, second }
     }
     pub fn fully_contained(&self, other: &CleaningAssignment) -> bool {
-        if self.first >= other.first && self.second <= other.second {
-            return true;
-        } else if other.first >= self.first && other.second <= self.second {
-            return true;
-        }
-        false
+        (self.first >= other.first && self.second <= other.second)
+            || (other.first >= self.first && other.second <= self.second)
     }
     pub fn overlap(&self, other: &CleaningAssignment) -> bool {
         // x1 <= y2 && y1 <= x2
         self.first <= other.second && other.first <= self.second
     }
 }
+
+#[derive(Debug)]
+pub struct Puzzle {
+    pairs: Vec<(CleaningAssignment, CleaningAssignment)>,
+}
+
+impl Puzzle {
+    fn count(&self, check: fn(&CleaningAssignment, &CleaningAssignment) -> bool) -> usize {
+        self.pairs
+            .iter()
+            .filter(|(elf1, elf2)| check(elf1, elf2))
+            .count()
+    }
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let re = Regex::new(r"^(\d+)-(\d+),(\d+)-(\d+)$").unwrap();
+        let pairs = parse_lines(input, |line| {
+            let caps = re
+                .captures(line)
+                .ok_or_else(|| ParseError::at(line, line, "a pair of ranges like 2-4,6-8"))?;
+            let assignment = |first: usize| -> Result<CleaningAssignment, ParseError> {
+                Ok(CleaningAssignment::new(
+                    number(line, caps.get(first).unwrap().as_str())?,
+                    number(line, caps.get(first + 1).unwrap().as_str())?,
+                ))
+            };
+            Ok((assignment(1)?, assignment(3)?))
+        })?;
+        Ok(Self { pairs })
+    }
+    fn part1(&self) -> Answer {
+        self.count(CleaningAssignment::fully_contained).into()
+    }
+    fn part2(&self) -> Answer {
+        self.count(CleaningAssignment::overlap).into()
+    }
+}
+
+impl Generate for Puzzle {
+    // `size` pairs of elves
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let section = |rng: &mut Rng| {
+            let start = rng.range(1..90);
+            (start, start + rng.range(0..10))
+        };
+        (0..size.max(1))
+            .map(|_| {
+                let (a, b) = section(rng);
+                let (c, d) = section(rng);
+                format!("{}-{},{}-{}\n", a, b, c, d)
+            })
+            .collect()
+    }
+}
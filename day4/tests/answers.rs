@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day4::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 2, 4);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 573, 867);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(100, 3);
+}
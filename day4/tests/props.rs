@@ -0,0 +1,28 @@
+use day4::CleaningAssignment;
+use proptest::prelude::*;
+
+// Sections first to second, as the assignment and the set of sections
+fn assignment() -> impl Strategy<Value = (CleaningAssignment, Vec<i32>)> {
+    (1..30_i32, 0..10_i32).prop_map(|(first, len)| {
+        let second = first + len;
+        (
+            CleaningAssignment::new(first, second),
+            (first..=second).collect(),
+        )
+    })
+}
+
+proptest! {
+    #[test]
+    fn overlap_is_symmetric((a, a_sections) in assignment(), (b, b_sections) in assignment()) {
+        prop_assert_eq!(a.overlap(&b), b.overlap(&a));
+        prop_assert_eq!(a.fully_contained(&b), b.fully_contained(&a));
+
+        // Checked section by section
+        let shared = a_sections.iter().filter(|section| b_sections.contains(section)).count();
+        prop_assert_eq!(a.overlap(&b), shared > 0);
+        let contained = shared == a_sections.len() || shared == b_sections.len();
+        prop_assert_eq!(a.fully_contained(&b), contained);
+        prop_assert!(a.overlap(&a) && a.fully_contained(&a));
+    }
+}
@@ -1,8 +1,17 @@
+use aoc_common::{blocks, number, Answer, Generate, ParseError, Rng, Solution};
+use regex::Regex;
+
 #[derive(Debug, Clone)]
 pub struct CrateStack {
     crates: Vec<char>,
 }
 
+impl Default for CrateStack {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CrateStack {
     pub fn new() -> Self {
         Self { crates: Vec::new() }
@@ -36,3 +45,144 @@ impl CrateStack {
         self.crates.last()
     }
 }
+
+pub fn array_mut_ref<T>(arr: &mut [T], a0: usize, a1: usize) -> (&mut T, &mut T) {
+    assert!(a0 != a1);
+    // SAFETY: this is safe because we know a0 != a1
+    unsafe {
+        (
+            &mut *(&mut arr[a0] as *mut _),
+            &mut *(&mut arr[a1] as *mut _),
+        )
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+struct Move {
+    qty: i32,
+    from: usize,
+    to: usize,
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    crates: Vec<CrateStack>,
+    moves: Vec<Move>,
+}
+
+impl Puzzle {
+    // Returns the crate on top of each stack once every move is done
+    fn rearrange(&self, one_at_a_time: bool) -> String {
+        let mut crates = self.crates.clone();
+
+        for m in self.moves.iter() {
+            let (a, b) = array_mut_ref(&mut crates, m.from - 1, m.to - 1);
+            if one_at_a_time {
+                b.transfer(a, m.qty);
+            } else {
+                b.transfer_part2(a, m.qty);
+            }
+        }
+        crates.iter().filter_map(|c| c.last()).collect()
+    }
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let [drawing, procedure] = blocks(input)[..] else {
+            return Err(ParseError::at_end(
+                input,
+                "a drawing and a procedure separated by a blank line",
+            ));
+        };
+        // The last line of the drawing numbers the stacks
+        let num_stacks = drawing.lines().last().unwrap().split_whitespace().count();
+        let mut crates: Vec<CrateStack> = vec![CrateStack::new(); num_stacks];
+
+        for line in drawing.lines() {
+            let columns = line.char_indices().skip(1).step_by(4).enumerate();
+            for (index, (pos, ch)) in columns {
+                if ch.is_ascii_uppercase() {
+                    if index >= num_stacks {
+                        return Err(ParseError::at(input, &line[pos..], "a crate over a stack"));
+                    }
+                    crates[index].add(ch);
+                }
+            }
+        }
+
+        let re = Regex::new(r"^move (\d+) from (\d+) to (\d+)$").unwrap();
+        let mut moves = Vec::new();
+        for line in procedure.lines() {
+            let caps = re
+                .captures(line)
+                .ok_or_else(|| ParseError::at(input, line, "move <n> from <n> to <n>"))?;
+            let stack = |index: usize| -> Result<usize, ParseError> {
+                let token = caps.get(index).unwrap().as_str();
+                match number(input, token)? {
+                    stack @ 1.. if stack <= num_stacks => Ok(stack),
+                    _ => Err(ParseError::at(
+                        input,
+                        token,
+                        format!("a stack from 1 to {}", num_stacks),
+                    )),
+                }
+            };
+            let m = Move {
+                qty: number(input, caps.get(1).unwrap().as_str())?,
+                from: stack(2)?,
+                to: stack(3)?,
+            };
+            if m.from == m.to {
+                let token = caps.get(3).unwrap().as_str();
+                return Err(ParseError::at(input, token, "a different stack to move to"));
+            }
+            moves.push(m);
+        }
+        Ok(Self { crates, moves })
+    }
+    fn part1(&self) -> Answer {
+        self.rearrange(true).into()
+    }
+    fn part2(&self) -> Answer {
+        self.rearrange(false).into()
+    }
+}
+
+impl Generate for Puzzle {
+    // Nine stacks and `size` moves, each only moving crates that are there
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        const STACKS: usize = 9;
+        let mut heights: Vec<usize> = (0..STACKS).map(|_| rng.range(1..9) as usize).collect();
+        let tallest = *heights.iter().max().unwrap();
+        let mut input = String::new();
+        for level in (0..tallest).rev() {
+            let row: Vec<String> = heights
+                .iter()
+                .map(|&height| match level < height {
+                    true => format!("[{}]", (b'A' + rng.below(26) as u8) as char),
+                    false => "   ".to_string(),
+                })
+                .collect();
+            input += &row.join(" ");
+            input.push('\n');
+        }
+        let numbers: Vec<String> = (1..=STACKS).map(|stack| format!(" {} ", stack)).collect();
+        input += &numbers.join(" ");
+        input += "\n\n";
+        for _ in 0..size.max(1) {
+            let from = loop {
+                let from = rng.below(STACKS);
+                if heights[from] > 0 {
+                    break from;
+                }
+            };
+            let to = (from + 1 + rng.below(STACKS - 1)) % STACKS;
+            let qty = 1 + rng.below(heights[from].min(6));
+            heights[from] -= qty;
+            heights[to] += qty;
+            input += &format!("move {} from {} to {}\n", qty, from + 1, to + 1);
+        }
+        input
+    }
+}
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day5::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", "CMZ", "MCD");
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", "TGWSMRBPN", "TZLTLWRNF");
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(50, 3);
+}
@@ -0,0 +1,87 @@
+use aoc_common::{parse_lines, Answer, Generate, ParseError, Rng, Solution};
+use std::collections::HashSet;
+
+// Returns the number of characters processed once the last `marker_len`
+// characters are all different, or None if they never are
+pub fn find_pattern(datastream: &[char], marker_len: usize) -> Option<usize> {
+    let mut index = 0;
+
+    while index + marker_len <= datastream.len() {
+        let mut a: HashSet<char> = HashSet::new();
+        let mut found = true;
+
+        for ch in &datastream[index..index + marker_len] {
+            if !a.insert(*ch) {
+                found = false;
+                break;
+            }
+        }
+        if found {
+            return Some(index + marker_len);
+        }
+        index += 1;
+    }
+    None
+}
+
+#[derive(Debug)]
+pub struct Puzzle {
+    datastreams: Vec<Vec<char>>,
+}
+
+impl Puzzle {
+    // The sample holds several datastreams, their answers are comma separated.
+    // None if any datastream has no marker
+    fn find_markers(&self, marker_len: usize) -> Answer {
+        let markers: Option<Vec<usize>> = self
+            .datastreams
+            .iter()
+            .map(|datastream| find_pattern(datastream, marker_len))
+            .collect();
+        let Some(markers) = markers else {
+            return Answer::None;
+        };
+        match markers[..] {
+            [marker] => marker.into(),
+            _ => markers
+                .iter()
+                .map(|marker| marker.to_string())
+                .collect::<Vec<String>>()
+                .join(",")
+                .into(),
+        }
+    }
+}
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let datastreams = parse_lines(input, |line| {
+            match line.find(|ch: char| !ch.is_ascii_lowercase()) {
+                Some(pos) => Err(ParseError::at(line, &line[pos..], "a letter a-z")),
+                None => Ok(line.chars().collect()),
+            }
+        })?;
+        Ok(Self { datastreams })
+    }
+    fn part1(&self) -> Answer {
+        self.find_markers(4)
+    }
+    fn part2(&self) -> Answer {
+        self.find_markers(14)
+    }
+}
+
+impl Generate for Puzzle {
+    // A datastream `size` characters long. Thirteen letters can't make a
+    // start-of-message marker, so it ends with the only one
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut letters: Vec<char> = ('a'..='z').collect();
+        rng.shuffle(&mut letters);
+        let mut datastream: String = (0..size.max(14) - 14)
+            .map(|_| letters[rng.below(13)])
+            .collect();
+        rng.shuffle(&mut letters[..14]);
+        datastream.extend(&letters[..14]);
+        datastream + "\n"
+    }
+}
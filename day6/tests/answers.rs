@@ -0,0 +1,26 @@
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day6::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", "7,5,6,10,11", "19,23,23,29,26");
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 1034, 2472);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(100, 3);
+}
+
+#[test]
+fn datastreams_without_markers() {
+    let puzzle = Puzzle::parse("abc\n").unwrap();
+    assert_eq!(puzzle.part1(), Answer::None);
+    let puzzle = Puzzle::parse("abcd\nabcdefghijklm\n").unwrap();
+    assert_eq!(puzzle.part1(), Answer::Text("4,4".to_string()));
+    assert_eq!(puzzle.part2(), Answer::None);
+}
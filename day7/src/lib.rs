@@ -1,4 +1,7 @@
+use aoc_common::{number, Answer, Generate, ParseError, Rng, Solution};
+use regex::RegexSet;
 use std::{cell::RefCell, rc::Rc};
+use tracing::trace;
 
 #[derive(Debug, Clone)]
 pub enum DirInfo {
@@ -37,11 +40,11 @@ impl DirEntry {
             DirInfo::Dir(ref mut v) => v.push(Rc::new(RefCell::new(Self::new_file(name, size)))),
         }
     }
-    pub fn get_size(&self) -> u32 {
+    pub fn get_size(&self) -> u64 {
         match self.info {
-            DirInfo::File(size) => size,
+            DirInfo::File(size) => size.into(),
             DirInfo::Dir(ref v) => {
-                let mut total: u32 = 0;
+                let mut total: u64 = 0;
                 for item in v {
                     total += item.borrow().get_size();
                 }
@@ -50,18 +53,16 @@ impl DirEntry {
         }
     }
 
-    pub fn get_subdir(&self, name: String) -> Rc<RefCell<DirEntry>> {
+    pub fn get_subdir(&self, name: &str) -> Option<Rc<RefCell<DirEntry>>> {
         match self.info {
-            DirInfo::File(_) => panic!("Can't get a subdir from a file!"),
-            DirInfo::Dir(ref v) => {
-                for item in v {
-                    let curr_item = item.borrow();
-                    if curr_item.name == name {
-                        return Rc::clone(item);
-                    }
-                }
-                panic!("No subdir with the name: {}", name);
-            }
+            DirInfo::File(_) => None,
+            DirInfo::Dir(ref v) => v
+                .iter()
+                .find(|item| {
+                    let item = item.borrow();
+                    item.name == name && matches!(item.info, DirInfo::Dir(_))
+                })
+                .map(Rc::clone),
         }
     }
 
@@ -81,7 +82,7 @@ impl DirEntry {
             }
         }
     }
-    pub fn get_less_than(&self, size: u32) -> u32 {
+    pub fn get_less_than(&self, size: u64) -> u64 {
         let mut total = 0;
         match self.info {
             DirInfo::File(_) => 0,
@@ -90,7 +91,7 @@ impl DirEntry {
                     total += item.borrow().get_less_than(size);
                 }
                 let dir_size = self.get_size();
-                println!("{} {}", self.name, dir_size);
+                trace!(dir = %self.name, size = dir_size, "directory");
                 if dir_size < size {
                     dir_size + total
                 } else {
@@ -99,27 +100,175 @@ impl DirEntry {
             }
         }
     }
-    pub fn get_smallest_dir_larger_than(&self, size: u32) -> u32 {
+    /// The size of the smallest directory here, this one included, larger
+    /// than `size`, if any is.
+    pub fn get_smallest_dir_larger_than(&self, size: u64) -> Option<u64> {
         match self.info {
-            DirInfo::File(_) => u32::MAX,
-            DirInfo::Dir(ref v) => {
-                let mut smallest = u32::MAX;
+            DirInfo::File(_) => None,
+            DirInfo::Dir(ref v) => v
+                .iter()
+                .filter_map(|item| item.borrow().get_smallest_dir_larger_than(size))
+                .chain(Some(self.get_size()).filter(|&our_size| our_size > size))
+                .min(),
+        }
+    }
+}
 
-                for item in v {
-                    let result = item.borrow().get_smallest_dir_larger_than(size);
-                    if result < smallest {
-                        smallest = result
-                    }
-                    if smallest == u32::MAX {
-                        // hasn't been found yet
-                        let our_size = self.get_size();
-                        if our_size > size {
-                            smallest = our_size;
-                        }
-                    }
+#[derive(Debug)]
+pub struct Puzzle {
+    root: Rc<RefCell<DirEntry>>,
+}
+
+// Real filesystems go a dozen or so directories deep. Walking and dropping
+// the tree recurses once per level, so much deeper input would overflow the
+// stack.
+const MAX_DEPTH: usize = 1000;
+
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let set = RegexSet::new([
+            r"^\$ cd /$",
+            r"^\$ cd \.\.$",
+            r"^\$ cd (.+)$",
+            r"^\$ ls$",
+            r"^dir (.+)$",
+            r"^(\d+) (.+)$",
+        ])
+        .unwrap();
+        let mut path: Vec<Rc<RefCell<DirEntry>>> =
+            vec![Rc::new(RefCell::new(DirEntry::new_dir("/".to_string())))];
+
+        for line in input.lines() {
+            let matches = set.matches(line);
+            if matches.matched(0) {
+                // go to root
+                path.truncate(1);
+            } else if matches.matched(1) {
+                // go up a directory
+                if path.len() == 1 {
+                    return Err(ParseError::at(input, line, "a directory to leave, not /"));
+                }
+                _ = path.pop();
+            } else if matches.matched(2) {
+                // descend a directory
+                let name = &line[5..];
+                if path.len() > MAX_DEPTH {
+                    let expected = format!("directories nested at most {} deep", MAX_DEPTH);
+                    return Err(ParseError::at(input, name, expected));
                 }
-                smallest
+                let new_dir = path
+                    .last()
+                    .unwrap()
+                    .borrow()
+                    .get_subdir(name)
+                    .ok_or_else(|| ParseError::at(input, name, "a directory listed by ls"))?;
+                path.push(new_dir);
+            } else if matches.matched(3) {
+                // ls - ignore
+            } else if matches.matched(4) {
+                let mut current_dir = path.last_mut().unwrap().borrow_mut();
+                current_dir.add_new_dir(line[4..].to_string());
+            } else if matches.matched(5) {
+                let (size, name) = line.split_once(' ').unwrap();
+                let size = number(input, size)?;
+                let mut current_dir = path.last_mut().unwrap().borrow_mut();
+                current_dir.add_new_file(name.to_string(), size);
+            } else {
+                return Err(ParseError::at(
+                    input,
+                    line,
+                    "a command or a directory listing",
+                ));
+            }
+        }
+        Ok(Self {
+            root: path.swap_remove(0),
+        })
+    }
+    fn part1(&self) -> Answer {
+        self.root.borrow().get_less_than(100_000).into()
+    }
+    fn part2(&self) -> Answer {
+        let total_disk_space = 70_000_000;
+        let needed_free_space = 30_000_000;
+        let root = self.root.borrow();
+        // Nothing needs freeing while the disk's already got the space, and
+        // more than the disk holds needs freeing when it's somehow overfull
+        let need_to_free = root
+            .get_size()
+            .saturating_sub(total_disk_space - needed_free_space);
+
+        root.get_smallest_dir_larger_than(need_to_free)
+            .map_or(Answer::None, Answer::from)
+    }
+}
+
+// A name for the nth directory, unique among its siblings
+fn dir_name(mut n: usize) -> String {
+    let mut name = String::new();
+    loop {
+        name.push((b'a' + (n % 26) as u8) as char);
+        n /= 26;
+        if n == 0 {
+            return name;
+        }
+    }
+}
+
+impl Generate for Puzzle {
+    // `size` directories, sometimes nested in the last one made to grow
+    // long chains, holding files that fill between 41M and 69M of the disk
+    // so there's always something to delete for the update
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let num_dirs = size.max(1);
+        let mut children: Vec<Vec<usize>> = vec![Vec::new(); num_dirs];
+        let mut depth = vec![0; num_dirs];
+        for dir in 1..num_dirs {
+            let mut parent = match rng.chance(30) {
+                true => dir - 1,
+                false => rng.below(dir),
+            };
+            if depth[parent] >= MAX_DEPTH - 1 {
+                parent = 0;
+            }
+            depth[dir] = depth[parent] + 1;
+            children[parent].push(dir);
+        }
+        let weights: Vec<Vec<u64>> = (0..num_dirs)
+            .map(|_| {
+                (0..rng.below(5))
+                    .map(|_| rng.range(1..1_000) as u64)
+                    .collect()
+            })
+            .collect();
+        let total_weight = weights.iter().flatten().sum::<u64>().max(1);
+        let disk_used = rng.range(41_000_000..69_000_000) as u64;
+
+        let mut input = String::from("$ cd /\n");
+        // Directories to list, with None to go back up once one's finished
+        let mut todo = vec![Some(0)];
+        while let Some(next) = todo.pop() {
+            let Some(dir) = next else {
+                input += "$ cd ..\n";
+                continue;
+            };
+            if dir != 0 {
+                input += &format!("$ cd {}\n", dir_name(dir));
+            }
+            input += "$ ls\n";
+            for &child in &children[dir] {
+                input += &format!("dir {}\n", dir_name(child));
+            }
+            for (file, weight) in weights[dir].iter().enumerate() {
+                let size = (weight * disk_used / total_weight).max(1);
+                let ext = rng.pick(&["", ".txt", ".dat", ".log", ".lst"]);
+                input += &format!("{} f{}{}\n", size, file, ext);
+            }
+            for &child in children[dir].iter().rev() {
+                todo.push(None);
+                todo.push(Some(child));
             }
         }
+        input
     }
 }
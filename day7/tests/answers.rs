@@ -0,0 +1,40 @@
+use aoc_common::{check, check_generated, check_input, Answer, Solution};
+use day7::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 95_437, 24_933_642);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 1_077_191, 5_649_896);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(50, 3);
+}
+
+#[test]
+fn disks_with_room_to_spare() {
+    // Under 40M used, so the update fits without deleting anything, and the
+    // smallest directory with anything in it will do
+    let puzzle = Puzzle::parse("$ cd /\n$ ls\ndir a\n5 b.txt\n$ cd a\n$ ls\n3 c.txt\n").unwrap();
+    assert_eq!(puzzle.part2(), Answer::Num(3));
+    assert_eq!(
+        Puzzle::parse("$ cd /\n$ ls\n").unwrap().part2(),
+        Answer::None
+    );
+}
+
+#[test]
+fn disks_overfull() {
+    // Far more than the disk holds is used, more than a u32 counts, so only
+    // deleting everything frees enough
+    let puzzle =
+        Puzzle::parse("$ cd /\n$ ls\ndir a\n4000000000 b.txt\n$ cd a\n$ ls\n4000000000 c.txt\n")
+            .unwrap();
+    assert_eq!(puzzle.part1(), Answer::Num(0));
+    assert_eq!(puzzle.part2(), Answer::Num(8_000_000_000));
+}
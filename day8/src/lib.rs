@@ -1,60 +1,41 @@
+use aoc_common::{
+    heat, Answer, Colour, Draw, Generate, Grid, Image, ParseError, Point, Rng, Solution, BACKGROUND,
+};
+
 #[derive(Debug)]
 pub struct Forest {
-    grid: Vec<Vec<u32>>,
+    grid: Grid<u32>,
 }
 
-impl Forest {
-    pub fn new() -> Self {
-        Self { grid: Vec::new() }
-    }
-    pub fn add_line(&mut self, line: String) {
-        let mut new_line: Vec<u32> = Vec::new();
-
-        for ch in line.chars() {
-            new_line.push(ch.to_digit(10).unwrap());
+// Trees seen looking out over `trees` from one of `height`, up to and
+// including the first that blocks the view
+fn viewing_distance<'a>(height: u32, trees: impl Iterator<Item = &'a u32>) -> u32 {
+    let mut total_trees = 0;
+    for &tree in trees {
+        total_trees += 1;
+        if tree >= height {
+            break;
         }
-        self.grid.push(new_line);
     }
+    total_trees
+}
 
+impl Forest {
     fn is_visible_from_left(&self, x: usize, y: usize) -> bool {
-        let height = self.grid[y][x];
-
-        for check_x in 0..x {
-            if self.grid[y][check_x] >= height {
-                return false;
-            }
-        }
-        return true;
+        let height = self.grid[Point::new(x, y)];
+        self.grid.row(y)[..x].iter().all(|&tree| tree < height)
     }
     fn is_visible_from_top(&self, x: usize, y: usize) -> bool {
-        let height = self.grid[y][x];
-
-        for check_y in 0..y {
-            if self.grid[check_y][x] >= height {
-                return false;
-            }
-        }
-        return true;
+        let height = self.grid[Point::new(x, y)];
+        self.grid.column(x).take(y).all(|&tree| tree < height)
     }
     fn is_visible_from_right(&self, x: usize, y: usize) -> bool {
-        let height = self.grid[y][x];
-
-        for check_x in x + 1..self.grid.len() {
-            if self.grid[y][check_x] >= height {
-                return false;
-            }
-        }
-        return true;
+        let height = self.grid[Point::new(x, y)];
+        self.grid.row(y)[x + 1..].iter().all(|&tree| tree < height)
     }
     fn is_visible_from_bottom(&self, x: usize, y: usize) -> bool {
-        let height = self.grid[y][x];
-
-        for check_y in y + 1..self.grid.len() {
-            if self.grid[check_y][x] >= height {
-                return false;
-            }
-        }
-        return true;
+        let height = self.grid[Point::new(x, y)];
+        self.grid.column(x).skip(y + 1).all(|&tree| tree < height)
     }
 
     fn is_visible(&self, x: usize, y: usize) -> bool {
@@ -64,118 +45,103 @@ impl Forest {
             || self.is_visible_from_bottom(x, y)
     }
     pub fn count_visible(&self) -> u32 {
-        let size = self.grid.len();
-        let mut total_visible = 0;
-        for y in 0..size {
-            for x in 0..size {
-                total_visible += if self.is_visible(x, y) { 1 } else { 0 }
-            }
-        }
-        total_visible
+        self.grid
+            .positions()
+            .filter(|pt| self.is_visible(pt.x, pt.y))
+            .count() as u32
     }
     fn get_scenic_top(&self, x: usize, y: usize) -> u32 {
-        let mut total_trees = 0;
-        let height = self.grid[y][x];
-        let mut new_y: i32 = y as i32 - 1;
-        let new_x = x;
-
-        while new_y >= 0 {
-            if self.grid[new_y as usize][new_x] >= height {
-                return total_trees + 1;
-            } else {
-                total_trees += 1;
-                new_y -= 1;
-            }
-        }
-        total_trees
+        viewing_distance(
+            self.grid[Point::new(x, y)],
+            self.grid.column(x).take(y).rev(),
+        )
     }
     fn get_scenic_bottom(&self, x: usize, y: usize) -> u32 {
-        let mut total_trees = 0;
-        let height = self.grid[y][x];
-        let mut new_y = y + 1;
-        let new_x = x;
-
-        while new_y < self.grid.len() {
-            if self.grid[new_y][new_x] >= height {
-                return total_trees + 1;
-            } else {
-                total_trees += 1;
-                new_y += 1;
-            }
-        }
-        total_trees
+        viewing_distance(self.grid[Point::new(x, y)], self.grid.column(x).skip(y + 1))
     }
     fn get_scenic_left(&self, x: usize, y: usize) -> u32 {
-        let mut total_trees = 0;
-        let height = self.grid[y][x];
-        let new_y = y;
-        let mut new_x: i32 = x as i32 - 1;
-
-        while new_x >= 0 {
-            if self.grid[new_y][new_x as usize] >= height {
-                return total_trees + 1;
-            } else {
-                total_trees += 1;
-                new_x -= 1;
-            }
-        }
-        total_trees
+        viewing_distance(
+            self.grid[Point::new(x, y)],
+            self.grid.row(y)[..x].iter().rev(),
+        )
     }
     fn get_scenic_right(&self, x: usize, y: usize) -> u32 {
-        let mut total_trees = 0;
-        let height = self.grid[y][x];
-        let new_y = y;
-        let mut new_x = x + 1;
-
-        while new_x < self.grid.len() {
-            if self.grid[new_y][new_x] >= height {
-                return total_trees + 1;
-            } else {
-                total_trees += 1;
-                new_x += 1;
-            }
-        }
-        total_trees
+        viewing_distance(
+            self.grid[Point::new(x, y)],
+            self.grid.row(y)[x + 1..].iter(),
+        )
     }
 
+    // Trees on the edge see nothing one way, so score 0
     fn get_scenic(&self, x: usize, y: usize) -> u32 {
-        let size = self.grid.len();
-
-        if x == 0 || x == size - 1 || y == 0 || y == size - 1 {
-            0
-        } else {
-            let total = self.get_scenic_top(x, y)
-                * self.get_scenic_left(x, y)
-                * self.get_scenic_right(x, y)
-                * self.get_scenic_bottom(x, y);
-            /*
-                        println!(
-                            "({} {}) {} {} {} {} {}",
-                            x,
-                            y,
-                            self.get_scenic_top(x, y),
-                            self.get_scenic_left(x, y),
-                            self.get_scenic_right(x, y),
-                            self.get_scenic_bottom(x, y),
-                            total
-                        );
-            */
-            total
-        }
+        self.get_scenic_top(x, y)
+            * self.get_scenic_left(x, y)
+            * self.get_scenic_right(x, y)
+            * self.get_scenic_bottom(x, y)
     }
 
     pub fn get_highest_scenic(&self) -> u32 {
-        let size = self.grid.len();
+        self.grid
+            .positions()
+            .map(|pt| self.get_scenic(pt.x, pt.y))
+            .max()
+            .unwrap_or(0)
+    }
+}
 
-        let mut largest = 0;
-        for y in 0..size {
-            for x in 0..size {
-                let scenic = self.get_scenic(x, y);
-                if scenic > largest {
-                    largest = scenic;
-                }
+impl Draw for Forest {
+    // Part 1 shows the trees seen from outside the forest in green and the
+    // hidden ones in grey. Part 2 is a heatmap of scenic scores with the
+    // best tree in white, on a log scale as most scores are tiny next to
+    // the best.
+    fn image(&self, part: u8) -> Image {
+        let mut image = Image::new(self.grid.width(), self.grid.height(), BACKGROUND);
+        if part == 1 {
+            for pt in self.grid.positions() {
+                let colour = match self.is_visible(pt.x, pt.y) {
+                    true => Colour::Green,
+                    false => Colour::Grey,
+                };
+                image[pt] = colour.rgb();
             }
+            return image;
+        }
+        let best = self.get_highest_scenic();
+        for pt in self.grid.positions() {
+            let score = self.get_scenic(pt.x, pt.y);
+            image[pt] = match score == best {
+                true => Colour::White.rgb(),
+                false => heat((score as f64).ln_1p(), (best as f64).ln_1p()),
+            };
         }
-        largest
+        image
+    }
+}
+
+impl Solution for Forest {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, "a tree height 0-9", |ch| ch.to_digit(10))?;
+        Ok(Forest { grid })
+    }
+    fn part1(&self) -> Answer {
+        self.count_visible().into()
+    }
+    fn part2(&self) -> Answer {
+        self.get_highest_scenic().into()
+    }
+}
+
+impl Generate for Forest {
+    // A forest `size` trees square
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let side = size.max(1);
+        (0..side)
+            .map(|_| {
+                let row: String = (0..side)
+                    .map(|_| (b'0' + rng.below(10) as u8) as char)
+                    .collect();
+                row + "\n"
+            })
+            .collect()
     }
 }
@@ -0,0 +1,17 @@
+use aoc_common::{check, check_generated, check_input};
+use day8::Forest;
+
+#[test]
+fn sample() {
+    check::<Forest>("sample.txt", 21, 8);
+}
+
+#[test]
+fn input() {
+    check_input::<Forest>("input.txt", 1840, 405_769);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Forest>(30, 3);
+}
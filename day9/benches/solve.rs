@@ -0,0 +1,10 @@
+use aoc_common::bench;
+use criterion::{criterion_group, criterion_main, Criterion};
+use day9::Puzzle;
+
+fn solve(c: &mut Criterion) {
+    bench::<Puzzle>(c, "day9", &["sample.txt", "sample2.txt", "input.txt"]);
+}
+
+criterion_group!(benches, solve);
+criterion_main!(benches);
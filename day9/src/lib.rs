@@ -1,129 +1,163 @@
+use aoc_common::{
+    number, parse_lines, Animate, Answer, Colour, Dir4, Frame, Generate, ParseError, Point, Rng,
+    Solution, SparseGrid,
+};
 use std::collections::HashMap;
-#[derive(Debug, Hash, PartialEq, Eq, Copy, Clone)]
-struct Point {
-    x: i32,
-    y: i32,
-}
 
 #[derive(Debug)]
 pub struct Rope {
-    head: Point,
-    tails: Vec<Point>,
-    tail_visited_points: HashMap<Point, u32>,
+    head: Point<i32>,
+    tails: Vec<Point<i32>>,
+    tail_visited_points: HashMap<Point<i32>, u32>,
 }
 
-impl Point {
-    pub fn new(x: i32, y: i32) -> Self {
-        Self { x, y }
+impl Default for Rope {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 impl Rope {
     pub fn new() -> Self {
+        Self::with_knots(10)
+    }
+    // num_knots includes the head
+    pub fn with_knots(num_knots: usize) -> Self {
         let mut tail_visited_points = HashMap::new();
         tail_visited_points.insert(Point::new(0, 0), 1);
-        let tails = vec![Point::new(0, 0); 9];
+        let tails = vec![Point::new(0, 0); num_knots - 1];
         Self {
             head: Point::new(0, 0),
-            tails: tails,
-            tail_visited_points: tail_visited_points,
+            tails,
+            tail_visited_points,
         }
     }
-    fn does_follower_need_move(head: &Point, tail: &Point) -> bool {
-        if (tail.x - head.x).abs() > 1 {
-            true
-        } else {
-            if (tail.y - head.y).abs() > 1 {
-                true
-            } else {
-                false
-            }
-        }
+    // A knot only moves once it stops touching the one ahead of it
+    fn does_follower_need_move(head: &Point<i32>, tail: &Point<i32>) -> bool {
+        head.chebyshev(*tail) > 1
     }
-    fn move_tail(head: &Point, tail: &mut Point) {
-        if head.x == tail.x {
-            if head.y > tail.y {
-                tail.y += 1
-            } else {
-                tail.y -= 1
-            }
-        } else if head.y == tail.y {
-            if head.x > tail.x {
-                tail.x += 1
-            } else {
-                tail.x -= 1
-            }
-        } else {
-            if head.x < tail.x {
-                tail.x -= 1;
-                if head.y < tail.y {
-                    tail.y -= 1;
-                } else {
-                    tail.y += 1;
-                }
-            } else {
-                tail.x += 1;
-                if head.y < tail.y {
-                    tail.y -= 1;
-                } else {
-                    tail.y += 1;
-                }
-            }
-        }
+    // Moves one step towards the knot ahead, diagonally if not in line
+    fn move_tail(head: &Point<i32>, tail: &mut Point<i32>) {
+        let diff = *head - *tail;
+        *tail += Point::new(diff.x.signum(), diff.y.signum());
     }
 
-    fn move_head(&mut self, amount_x: i32, amount_y: i32) {
-        self.head.x += amount_x;
-        self.head.y += amount_y;
+    fn move_head(&mut self, dir: Dir4) {
+        self.head += dir.offset();
         for i in 0..self.tails.len() {
-            let leader = if (i == 0) {
-                self.head
-            } else {
-                self.tails[i - 1]
-            };
+            let leader = if i == 0 { self.head } else { self.tails[i - 1] };
             if Self::does_follower_need_move(&leader, &self.tails[i]) {
                 Self::move_tail(&leader, &mut self.tails[i]);
                 if i == (self.tails.len() - 1) {
-                    self.tail_visited_points.entry(self.tails[i]).or_insert(0);
-                    let num_times_visited =
-                        self.tail_visited_points.get(&self.tails[i]).unwrap() + 1;
-                    _ = self
-                        .tail_visited_points
-                        .insert(self.tails[i], num_times_visited);
+                    *self.tail_visited_points.entry(self.tails[i]).or_insert(0) += 1;
                 }
             }
         }
     }
 
-    pub fn up(&mut self, distance: i32) {
+    pub fn move_by(&mut self, dir: Dir4, distance: i32) {
         for _ in 0..distance {
-            self.move_head(0, 1);
+            self.move_head(dir);
         }
     }
 
-    pub fn down(&mut self, distance: i32) {
-        for _ in 0..distance {
-            self.move_head(0, -1);
+    pub fn get_num_tail_visited(&self) -> usize {
+        self.tail_visited_points.keys().len()
+    }
+    // Where the tail has been, with the knots drawn over it and the head
+    // over them
+    pub fn frame(&self, caption: impl Into<String>) -> Frame {
+        let wide = |pt: Point<i32>| Point::new(pt.x as i64, pt.y as i64);
+        let mut cells: SparseGrid<(char, Colour)> = self
+            .tail_visited_points
+            .keys()
+            .map(|&pt| (wide(pt), ('#', Colour::Grey)))
+            .collect();
+        cells.insert(Point::new(0, 0), ('s', Colour::Cyan));
+        for (i, &knot) in self.tails.iter().enumerate().rev() {
+            let label = char::from_digit(i as u32 + 1, 36).unwrap_or('*');
+            cells.insert(wide(knot), (label, Colour::Yellow));
         }
+        cells.insert(wide(self.head), ('H', Colour::Red));
+
+        let (min, max) = cells.bounds().unwrap_or_default();
+        let head = wide(self.head) - min;
+        Frame::from_sparse(&cells, (min, max), caption, |cell| {
+            cell.copied().unwrap_or((' ', Colour::Plain))
+        })
+        .with_focus(Point::new(head.x as usize, head.y as usize))
     }
+}
 
-    pub fn left(&mut self, distance: i32) {
-        for _ in 0..distance {
-            self.move_head(-1, 0);
+#[derive(Debug)]
+pub struct Puzzle {
+    moves: Vec<(Dir4, i32)>,
+}
+
+impl Puzzle {
+    fn simulate(&self, num_knots: usize) -> usize {
+        let mut rope = Rope::with_knots(num_knots);
+
+        for &(direction, distance) in self.moves.iter() {
+            rope.move_by(direction, distance);
         }
+        rope.get_num_tail_visited()
     }
+}
 
-    pub fn right(&mut self, distance: i32) {
-        for _ in 0..distance {
-            self.move_head(1, 0);
-        }
+impl Animate for Puzzle {
+    // A frame for each step the head takes
+    fn frames(&self, part: u8) -> Box<dyn Iterator<Item = Frame> + '_> {
+        let mut rope = Rope::with_knots(if part == 1 { 2 } else { 10 });
+        let steps = self
+            .moves
+            .iter()
+            .flat_map(|&(dir, distance)| (0..distance).map(move |_| dir));
+        Box::new(steps.map(move |dir| {
+            rope.move_head(dir);
+            let caption = format!(
+                "Day 9 part {}: the tail has visited {}",
+                part,
+                rope.get_num_tail_visited()
+            );
+            rope.frame(caption)
+        }))
     }
+}
 
-    pub fn get_num_tail_visited(&self) -> usize {
-        self.tail_visited_points.keys().len()
+impl Solution for Puzzle {
+    fn parse(input: &str) -> Result<Self, ParseError> {
+        let moves = parse_lines(input, |line| {
+            let mut tokens = line.split_whitespace();
+            let direction = match tokens.next() {
+                Some("L") => Dir4::Left,
+                Some("R") => Dir4::Right,
+                Some("D") => Dir4::Down,
+                Some("U") => Dir4::Up,
+                Some(token) => return Err(ParseError::at(line, token, "L, R, D or U")),
+                None => return Err(ParseError::at_end(line, "a direction")),
+            };
+            let distance = match tokens.next() {
+                Some(token) => number(line, token)?,
+                None => return Err(ParseError::at_end(line, "a distance")),
+            };
+            Ok((direction, distance))
+        })?;
+        Ok(Self { moves })
     }
-    pub fn display(&self) {
-        println!("H: {:?} T:{:?}", self.head, self.tails);
+    fn part1(&self) -> Answer {
+        self.simulate(2).into()
+    }
+    fn part2(&self) -> Answer {
+        self.simulate(10).into()
+    }
+}
+
+impl Generate for Puzzle {
+    // `size` moves of the head
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        (0..size.max(1))
+            .map(|_| format!("{} {}\n", rng.pick(&['U', 'D', 'L', 'R']), rng.range(1..20)))
+            .collect()
     }
 }
-// let val = m.entry(k).or_insert(d);
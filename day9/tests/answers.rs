@@ -0,0 +1,22 @@
+use aoc_common::{check, check_generated, check_input};
+use day9::Puzzle;
+
+#[test]
+fn sample() {
+    check::<Puzzle>("sample.txt", 13, 1);
+}
+
+#[test]
+fn sample2() {
+    check::<Puzzle>("sample2.txt", 88, 36);
+}
+
+#[test]
+fn input() {
+    check_input::<Puzzle>("input.txt", 6044, 2384);
+}
+
+#[test]
+fn generated() {
+    check_generated::<Puzzle>(100, 3);
+}
@@ -0,0 +1,23 @@
+#![no_main]
+
+use aoc_common::Solution;
+use libfuzzer_sys::fuzz_target;
+
+// Solving scans rows as wide as the sensors reach, so only small inputs
+// with small coordinates are solved, to keep each run quick
+fn small(input: &str) -> bool {
+    input.len() <= 1_000
+        && input
+            .split(|ch: char| !ch.is_ascii_digit())
+            .filter(|digits| !digits.is_empty())
+            .all(|digits| digits.len() <= 3)
+}
+
+fuzz_target!(|input: &str| {
+    if let Ok(network) = day15::SensorNetwork::parse(input) {
+        if small(input) {
+            network.part1();
+            network.part2();
+        }
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc_common::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(cave) = day16::Cave::parse(input) {
+        // The search grows with every flowing valve, so only small caves
+        // are solved, to keep each run quick
+        if input.len() <= 600 {
+            cave.part1();
+            cave.part2();
+        }
+    }
+});
@@ -0,0 +1,15 @@
+#![no_main]
+
+use aoc_common::Solution;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(puzzle) = day24::Puzzle::parse(input) {
+        // The blizzards' cycle grows with the valley's area, so only small
+        // valleys are solved, to keep each run quick
+        if input.len() <= 1_000 {
+            puzzle.part1();
+            puzzle.part2();
+        }
+    }
+});